@@ -1,8 +1,3 @@
--- Pupose of this command: disable rucursive triggers because we don't want the delete trigger to activates the update trigger.
-PRAGMA recursive_triggers = OFF;
--- Pupose of this command: activate foreign key constraints and delete on CASCADE.
-PRAGMA foreign_keys = ON;
-
 CREATE TABLE IF NOT EXISTS Workspace (
   id INTEGER PRIMARY KEY AUTOINCREMENT,
   name TEXT NOT NULL UNIQUE,
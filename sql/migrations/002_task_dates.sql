@@ -0,0 +1,2 @@
+ALTER TABLE Task ADD COLUMN due_date datetime;
+ALTER TABLE Task ADD COLUMN start_date datetime;
@@ -0,0 +1,3 @@
+-- Purpose of this migration: the old schema inserted a row in trigger_control every time the app started, keep only one.
+DELETE FROM trigger_control WHERE rowid NOT IN (SELECT MIN(rowid) FROM trigger_control);
+UPDATE trigger_control SET active = 0;
@@ -0,0 +1,33 @@
+-- Purpose of this migration: full-text index of the task names and descriptions, kept in sync by triggers.
+CREATE VIRTUAL TABLE IF NOT EXISTS TaskSearch USING fts5(
+  name,
+  description,
+  content = 'Task',
+  content_rowid = 'id',
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS updateTaskSearchOnTaskInsert
+AFTER
+INSERT ON Task BEGIN
+  INSERT INTO TaskSearch (rowid, name, description)
+  VALUES (NEW.id, NEW.name, NEW.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS updateTaskSearchOnTaskDelete
+AFTER
+DELETE ON Task BEGIN
+  INSERT INTO TaskSearch (TaskSearch, rowid, name, description)
+  VALUES ('delete', OLD.id, OLD.name, OLD.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS updateTaskSearchOnTaskUpdate
+AFTER
+UPDATE OF name, description ON Task BEGIN
+  INSERT INTO TaskSearch (TaskSearch, rowid, name, description)
+  VALUES ('delete', OLD.id, OLD.name, OLD.description);
+  INSERT INTO TaskSearch (rowid, name, description)
+  VALUES (NEW.id, NEW.name, NEW.description);
+END;
+
+INSERT INTO TaskSearch (TaskSearch) VALUES ('rebuild');
@@ -0,0 +1,21 @@
+-- Purpose of this migration: tags shared by the tasks of every workspace.
+CREATE TABLE IF NOT EXISTS Tag (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL UNIQUE COLLATE NOCASE
+);
+CREATE TABLE IF NOT EXISTS TaskTag (
+  taskid INTEGER NOT NULL,
+  tagid INTEGER NOT NULL,
+  PRIMARY KEY (taskid, tagid),
+  FOREIGN KEY (taskid) REFERENCES Task(id) ON DELETE CASCADE,
+  FOREIGN KEY (tagid) REFERENCES Tag(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS TaskTagByTag ON TaskTag (tagid);
+
+CREATE TRIGGER IF NOT EXISTS removeUnusedTagOnTaskTagDelete
+AFTER
+DELETE ON TaskTag
+WHEN NOT EXISTS (SELECT 1 FROM TaskTag WHERE tagid = OLD.tagid)
+BEGIN
+  DELETE FROM Tag WHERE id = OLD.tagid;
+END;
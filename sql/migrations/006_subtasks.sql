@@ -0,0 +1,97 @@
+-- Purpose of this migration: tasks can have subtasks, the order of a task is kept among the tasks sharing its parent.
+ALTER TABLE Task ADD COLUMN parentid INTEGER REFERENCES Task(id) ON DELETE CASCADE;
+CREATE INDEX IF NOT EXISTS TaskByParent ON Task (parentid);
+
+DROP TRIGGER IF EXISTS updateTaskOrderOnTaskUpdate;
+DROP TRIGGER IF EXISTS updateTaskOrderOnTaskNullInsert;
+DROP TRIGGER IF EXISTS updateTaskOrderOnTaskNotNullInsert;
+DROP TRIGGER IF EXISTS updateTaskOrderOnTaskDelete;
+
+CREATE TRIGGER IF NOT EXISTS updateTaskOrderOnTaskUpdate
+BEFORE
+UPDATE OF task_order ON Task
+WHEN OLD.parentid IS NEW.parentid AND (SELECT active FROM trigger_control) = 0
+BEGIN
+  UPDATE trigger_control SET active = 1;
+
+  UPDATE Task
+  SET task_order = task_order - 1
+  WHERE NEW.task_order > OLD.task_order
+  AND task_order > OLD.task_order
+  AND task_order <= NEW.task_order
+  AND workspaceid == NEW.workspaceid
+  AND parentid IS NEW.parentid;
+
+  UPDATE Task
+  SET task_order = task_order + 1
+  WHERE NEW.task_order < OLD.task_order
+  AND task_order < OLD.task_order
+  AND task_order >= NEW.task_order
+  AND workspaceid == NEW.workspaceid
+  AND parentid IS NEW.parentid;
+
+  UPDATE trigger_control SET active = 0;
+END;
+
+-- moving a task to another parent closes its place among its old siblings and opens one among the new ones.
+CREATE TRIGGER IF NOT EXISTS updateTaskOrderOnTaskParentUpdate
+BEFORE
+UPDATE OF parentid ON Task
+WHEN OLD.parentid IS NOT NEW.parentid AND (SELECT active FROM trigger_control) = 0
+BEGIN
+  UPDATE trigger_control SET active = 1;
+
+  UPDATE Task
+  SET task_order = task_order - 1
+  WHERE task_order > OLD.task_order
+  AND workspaceid == OLD.workspaceid
+  AND parentid IS OLD.parentid;
+
+  UPDATE Task
+  SET task_order = task_order + 1
+  WHERE task_order >= NEW.task_order
+  AND workspaceid == NEW.workspaceid
+  AND parentid IS NEW.parentid;
+
+  UPDATE trigger_control SET active = 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS updateTaskOrderOnTaskNullInsert
+AFTER
+INSERT ON Task
+WHEN NEW.task_order IS NULL AND (SELECT active FROM trigger_control) = 0
+BEGIN
+  UPDATE trigger_control SET active = 1;
+  UPDATE Task
+  SET task_order = (SELECT COALESCE(MAX(task_order), -1) + 1 FROM Task WHERE workspaceid = NEW.workspaceid AND parentid IS NEW.parentid)
+  WHERE id = NEW.id;
+  UPDATE trigger_control SET active = 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS updateTaskOrderOnTaskNotNullInsert
+BEFORE
+INSERT ON Task
+WHEN NEW.task_order IS NOT NULL AND (SELECT active FROM trigger_control) = 0
+BEGIN
+  UPDATE trigger_control SET active = 1;
+  UPDATE Task
+  SET task_order = task_order + 1
+  WHERE task_order >= NEW.task_order
+  AND workspaceid == NEW.workspaceid
+  AND parentid IS NEW.parentid;
+  UPDATE trigger_control SET active = 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS updateTaskOrderOnTaskDelete
+BEFORE
+DELETE ON Task
+WHEN (SELECT active FROM trigger_control) = 0
+BEGIN
+  UPDATE trigger_control SET active = 1;
+  UPDATE Task
+  SET task_order = task_order - 1
+  WHERE task_order > OLD.task_order
+  AND workspaceid == OLD.workspaceid
+  AND parentid IS OLD.parentid;
+  UPDATE trigger_control SET active = 0;
+END;
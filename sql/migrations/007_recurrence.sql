@@ -0,0 +1,2 @@
+-- Purpose of this migration: tasks can recur, the rule is stored the way it is typed (`weekly mon,fri`).
+ALTER TABLE Task ADD COLUMN recurrence TEXT;
@@ -0,0 +1,6 @@
+-- Purpose of this migration: removed tasks and workspaces go to a trash, they are kept as JSON so they can be restored as they were.
+CREATE TABLE IF NOT EXISTS Trash (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  content TEXT NOT NULL,
+  deleted_at datetime NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,2 @@
+-- Purpose of this migration: tasks have a status from the configuration, NULL stands for the first status matching `completed`.
+ALTER TABLE Task ADD COLUMN status TEXT;
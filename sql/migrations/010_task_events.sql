@@ -0,0 +1,30 @@
+-- Purpose of this migration: tasks remember when they were last completed, and what happens to them is kept in an append-only TaskEvent table. the events outlive their task so the history of a removed task can still be read, the existing tasks start with their creation.
+ALTER TABLE Task ADD COLUMN completed_at datetime;
+
+CREATE TABLE IF NOT EXISTS TaskEvent (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  taskid INTEGER NOT NULL,
+  workspaceid INTEGER NOT NULL,
+  task_name TEXT NOT NULL,
+  kind TEXT NOT NULL,
+  old_value TEXT,
+  new_value TEXT,
+  created_at datetime NOT NULL
+);
+CREATE INDEX IF NOT EXISTS TaskEventTask ON TaskEvent(taskid);
+CREATE INDEX IF NOT EXISTS TaskEventDate ON TaskEvent(created_at);
+
+CREATE TRIGGER IF NOT EXISTS preventTaskEventUpdate
+BEFORE UPDATE ON TaskEvent
+BEGIN
+  SELECT RAISE(ABORT, 'the task events can not be changed');
+END;
+
+CREATE TRIGGER IF NOT EXISTS preventTaskEventDelete
+BEFORE DELETE ON TaskEvent
+BEGIN
+  SELECT RAISE(ABORT, 'the task events can not be removed');
+END;
+
+INSERT INTO TaskEvent (taskid, workspaceid, task_name, kind, created_at)
+SELECT id, workspaceid, name, 'created', create_date FROM Task;
@@ -0,0 +1,4 @@
+-- Pupose of this command: disable rucursive triggers because we don't want the delete trigger to activates the update trigger.
+PRAGMA recursive_triggers = OFF;
+-- Pupose of this command: activate foreign key constraints and delete on CASCADE.
+PRAGMA foreign_keys = ON;
@@ -1,6 +1,7 @@
-use crate::app::ComponentId;
+use crate::app::{ComponentId, Mode};
+use crate::stats::Stats;
 use crate::structs::*;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
@@ -14,11 +15,14 @@ pub enum Action {
     Quit,
     ClearScreen,
     Error(String),
+    Info(String),
     Help,
     GoUp,
     GoDown,
     GoToTop,
     GoToBottom,
+    GoLeft,
+    GoRight,
     LeaveInsertMode,
     EnterInsertMode,
     AddItemBefore,
@@ -29,13 +33,23 @@ pub enum Action {
     MoveItemDown,
     MoveItemTop,
     MoveItemBottom,
+    MoveItemLeft,
+    MoveItemRight,
+    IndentItem,
+    OutdentItem,
+    ToggleFold,
     SendKeyEvent(KeyEvent),
+    SendMouseEvent(MouseEvent),
     AddTask(AddTask),
     AddWorkspace(AddWorkspace),
     UpdateTask(UpdateTask),
+    /// updates made by one action, like a sort, undone together.
+    UpdateTasks(Vec<UpdateTask>),
     UpdateWorkspace(UpdateWorkspace),
     RemoveTask(i32),
     RemoveWorkspace(i32),
+    Undo,
+    Redo,
     RequestTasksData(i32),
     RequestWorkspacesData,
     NewTasksData((Vec<Task>, i32)),
@@ -47,7 +61,15 @@ pub enum Action {
     FocusOnTasks,
     FocusOnWorkspaces,
     ToggleCompletion,
+    NextStatus,
+    PreviousStatus,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    FilterTags,
     EditDescription,
+    EditDueDate,
+    EditRecurrence,
     IncreasePriority,
     DecreasePriority,
     SortTasks(TaskSorter),
@@ -58,34 +80,99 @@ pub enum Action {
     SetupSortMenu(ComponentId),
     Cancel,
     OpenSortMenu,
+    OpenTaskSearch,
+    SetupTaskSearch(ComponentId),
+    ExitTaskSearch(ComponentId),
+    SearchTasks(String),
+    NewSearchResults(Vec<SearchResult>),
+    JumpToTask(Task),
+    SetupHelp(ComponentId),
+    ExitHelp(ComponentId),
+    PendingKeys(Vec<KeyEvent>),
+    SetupStatusBar(Mode, ComponentId),
+    OpenTrash,
+    SetupTrash(ComponentId),
+    ExitTrash(ComponentId),
+    RequestTrashData,
+    NewTrashData(Vec<TrashItem>),
+    RestoreTrashItem(i32),
+    PurgeTrashItem(i32),
+    EmptyTrash,
+    OpenTaskHistory,
+    SetupTaskHistory(ComponentId),
+    ExitTaskHistory(ComponentId),
+    RequestTaskHistory(i32),
+    NewTaskHistory(Vec<TaskEvent>),
+    OpenStatistics,
+    SetupStatistics(ComponentId),
+    ExitStatistics(ComponentId),
+    RequestStatistics,
+    NewStatistics(Stats),
+    AskConfirmation(String, Box<Action>),
+    SetupConfirmation(ComponentId),
+    ExitConfirmation(ComponentId),
+    EditDescriptionInEditor,
+    OpenEditor(i32, String),
+    SelectTask(Option<Task>),
+    ToggleDetails,
+    GrowWorkspaces,
+    ShrinkWorkspaces,
+    ToggleWorkspaces,
+    CycleLayout,
+    ToggleBoard,
+    ShowBoard(bool),
+    ToggleBoardColumns,
 }
 
 impl Action {
     pub fn get_target(&self) -> ComponentId {
         match self {
-            Action::AddTask(_) | Action::UpdateTask(_) | Action::RemoveTask(_) => {
-                ComponentId::DatabaseSetTasks
-            }
+            Action::AddTask(_)
+            | Action::UpdateTask(_)
+            | Action::UpdateTasks(_)
+            | Action::RemoveTask(_) => ComponentId::DatabaseSetTasks,
 
             Action::AddWorkspace(_) | Action::UpdateWorkspace(_) | Action::RemoveWorkspace(_) => {
                 ComponentId::DatabaseSetWorkspaces
             }
 
-            Action::RequestTasksData(_) | Action::RequestWorkspacesData => ComponentId::DatabaseGet,
+            Action::Undo | Action::Redo => ComponentId::DatabaseHistory,
+
+            Action::RestoreTrashItem(_) | Action::PurgeTrashItem(_) | Action::EmptyTrash => {
+                ComponentId::DatabaseTrash
+            }
+
+            Action::RequestTasksData(_)
+            | Action::RequestWorkspacesData
+            | Action::SearchTasks(_)
+            | Action::RequestTrashData
+            | Action::RequestTaskHistory(_)
+            | Action::RequestStatistics => ComponentId::DatabaseGet,
 
-            Action::NewTasksData(_) | Action::HighlightTask(_) => ComponentId::Tasks,
+            Action::HighlightTask(_) => ComponentId::Tasks,
 
-            Action::NewWorkspacesData(_) | Action::HighlightWorkspace(_) => ComponentId::Workspaces,
+            Action::HighlightWorkspace(_) => ComponentId::Workspaces,
 
             Action::GoUp
             | Action::GoDown
             | Action::GoToTop
             | Action::GoToBottom
+            | Action::GoLeft
+            | Action::GoRight
             | Action::AddItemAfter
             | Action::AddItemBefore
             | Action::DeleteItem
             | Action::EditDescription
+            | Action::EditDescriptionInEditor
+            | Action::EditDueDate
+            | Action::EditRecurrence
             | Action::ToggleCompletion
+            | Action::NextStatus
+            | Action::PreviousStatus
+            | Action::Search
+            | Action::NextMatch
+            | Action::PreviousMatch
+            | Action::FilterTags
             | Action::IncreasePriority
             | Action::DecreasePriority
             | Action::EditItem
@@ -93,11 +180,24 @@ impl Action {
             | Action::MoveItemDown
             | Action::MoveItemTop
             | Action::MoveItemBottom
-            | Action::SendKeyEvent(..) => ComponentId::Focused,
+            | Action::MoveItemLeft
+            | Action::MoveItemRight
+            | Action::ToggleBoardColumns
+            | Action::IndentItem
+            | Action::OutdentItem
+            | Action::ToggleFold
+            | Action::SendKeyEvent(..)
+            | Action::SendMouseEvent(..) => ComponentId::Focused,
 
-            Action::SortTasks(_) => ComponentId::Tasks,
             Action::SortWorkspaces(_) => ComponentId::Workspaces,
             Action::ToggleSortDirection | Action::SetupSortMenu(_) => ComponentId::SortMenu,
+            Action::SetupTaskSearch(_) | Action::NewSearchResults(_) => ComponentId::TaskSearch,
+            Action::SetupHelp(_) => ComponentId::Help,
+            Action::SetupStatusBar(..) => ComponentId::StatusBar,
+            Action::SetupTrash(_) | Action::NewTrashData(_) => ComponentId::Trash,
+            Action::SetupTaskHistory(_) | Action::NewTaskHistory(_) => ComponentId::TaskHistory,
+            Action::SetupStatistics(_) | Action::NewStatistics(_) => ComponentId::Statistics,
+            Action::SetupConfirmation(_) => ComponentId::Confirmation,
             Action::Select | Action::Cancel => ComponentId::Focused,
 
             Action::Tick
@@ -108,6 +208,28 @@ impl Action {
             | Action::Quit
             | Action::ClearScreen
             | Action::Error(_)
+            | Action::Info(_)
+            | Action::NewTasksData(_)
+            | Action::NewWorkspacesData(_)
+            | Action::SortTasks(_)
+            | Action::PendingKeys(_)
+            | Action::OpenTrash
+            | Action::ExitTrash(_)
+            | Action::OpenTaskHistory
+            | Action::ExitTaskHistory(_)
+            | Action::OpenStatistics
+            | Action::ExitStatistics(_)
+            | Action::AskConfirmation(..)
+            | Action::OpenEditor(..)
+            | Action::SelectTask(_)
+            | Action::ToggleDetails
+            | Action::GrowWorkspaces
+            | Action::ShrinkWorkspaces
+            | Action::ToggleWorkspaces
+            | Action::CycleLayout
+            | Action::ToggleBoard
+            | Action::ShowBoard(_)
+            | Action::ExitConfirmation(_)
             | Action::Help
             | Action::LeaveInsertMode
             | Action::EnterInsertMode
@@ -116,7 +238,11 @@ impl Action {
             | Action::FocusOnTasks
             | Action::FocusOnWorkspaces
             | Action::ExitSortMenu(_)
-            | Action::OpenSortMenu => ComponentId::All,
+            | Action::OpenSortMenu
+            | Action::OpenTaskSearch
+            | Action::ExitTaskSearch(_)
+            | Action::JumpToTask(_)
+            | Action::ExitHelp(_) => ComponentId::All,
         }
     }
 }
@@ -1,25 +1,41 @@
 use crate::{
     action::Action,
     components::{
-        fps::FpsCounter, sort_menu::SortMenu, tasks::TasksComponent,
+        board::Board, confirmation::Confirmation, fps::FpsCounter, help::Help,
+        notifications::Notifications, sort_menu::SortMenu, statistics::Statistics,
+        status_bar::StatusBar, task_details::TaskDetails, task_history::TaskHistory,
+        task_search::TaskSearch, tasks::TasksComponent, trash::Trash, which_key::WhichKey,
         workspaces::WorkspacesComponent, Component,
     },
-    config::Config,
-    database_ops::DatabaseOperations,
+    config::{Config, PROJECT_NAME},
+    database_ops::{DatabaseOperations, DATABASE_FILE},
     errors::DoMeError,
+    layout::{PaneLayout, LAYOUT_FILE},
+    stats::{Stats, STATS_DAYS},
+    structs::UpdateTask,
     tui::{Event, Tui},
 };
+use chrono::Local;
 use color_eyre::Result;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::{Constraint, Layout},
     prelude::Rect,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    io::{self, Write},
+    process,
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc;
 use tracing::{error, info};
 
+/// maximum number of tasks shown by the search popup.
+const SEARCH_RESULTS_LIMIT: usize = 50;
+
 pub struct App {
     config: Config,
     database: DatabaseOperations,
@@ -30,10 +46,15 @@ pub struct App {
     should_quit: bool,
     should_suspend: bool,
     mode: Mode,
-    last_key_events: (Vec<KeyEvent>, Option<usize>),
+    /// the keys of a sequence being typed and when they stop waiting for the next key.
+    pending_keys: (Vec<KeyEvent>, Option<Instant>),
     action_tx: mpsc::UnboundedSender<Action>,
     action_rx: mpsc::UnboundedReceiver<Action>,
     selected_workspace: Option<i32>,
+    show_details: bool,
+    layout: PaneLayout,
+    /// the workspaces whose tasks are shown as a board.
+    boards: HashSet<i32>,
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -41,11 +62,24 @@ pub enum ComponentId {
     #[default]
     Workspaces,
     Tasks,
+    Board,
     FpsCounter,
     DatabaseGet,
     DatabaseSetTasks,
     DatabaseSetWorkspaces,
+    DatabaseHistory,
+    DatabaseTrash,
     SortMenu,
+    TaskSearch,
+    Help,
+    WhichKey,
+    Notifications,
+    StatusBar,
+    TaskDetails,
+    Trash,
+    TaskHistory,
+    Statistics,
+    Confirmation,
     All,
     Focused,
 }
@@ -62,16 +96,38 @@ impl App {
     pub fn new(tick_rate: f64, frame_rate: f64) -> Result<Self> {
         let (action_tx, action_rx) = mpsc::unbounded_channel();
         let mut components: HashMap<ComponentId, Box<dyn Component>> = HashMap::new();
-        let config = Config::new()?;
+        // a broken configuration should not keep the tasks out of reach, the defaults are used.
+        let config = Config::new().unwrap_or_else(|e| {
+            let _ = action_tx.send(Action::Error(format!("Invalid configuration: {e}")));
+            Config::fallback()
+        });
+        // the layout changed at runtime is kept over the configured one.
+        let layout = PaneLayout::load(&config.config.data_dir.join(LAYOUT_FILE))
+            .unwrap_or_else(|e| {
+                let _ = action_tx.send(Action::Error(format!("Invalid saved layout: {e}")));
+                None
+            })
+            .unwrap_or(config.config.layout);
         components.insert(ComponentId::FpsCounter, Box::new(FpsCounter::new()));
         components.insert(
             ComponentId::Workspaces,
             Box::new(WorkspacesComponent::new()),
         );
         components.insert(ComponentId::Tasks, Box::new(TasksComponent::new()));
+        components.insert(ComponentId::Board, Box::new(Board::new()));
         components.insert(ComponentId::SortMenu, Box::new(SortMenu::new()));
+        components.insert(ComponentId::TaskSearch, Box::new(TaskSearch::new()));
+        components.insert(ComponentId::Help, Box::new(Help::new()));
+        components.insert(ComponentId::WhichKey, Box::new(WhichKey::new()));
+        components.insert(ComponentId::Notifications, Box::new(Notifications::new()));
+        components.insert(ComponentId::StatusBar, Box::new(StatusBar::new()));
+        components.insert(ComponentId::TaskDetails, Box::new(TaskDetails::new()));
+        components.insert(ComponentId::Trash, Box::new(Trash::new()));
+        components.insert(ComponentId::TaskHistory, Box::new(TaskHistory::new()));
+        components.insert(ComponentId::Statistics, Box::new(Statistics::new()));
+        components.insert(ComponentId::Confirmation, Box::new(Confirmation::new()));
         Ok(Self {
-            database: DatabaseOperations::new(config.config.data_dir.join("do_me.sqlite")),
+            database: DatabaseOperations::new(config.config.data_dir.join(DATABASE_FILE)),
             tick_rate,
             frame_rate,
             components,
@@ -80,17 +136,21 @@ impl App {
             should_suspend: false,
             config,
             mode: Mode::default(),
-            last_key_events: (Vec::new(), None),
+            pending_keys: (Vec::new(), None),
             action_tx,
             action_rx,
             selected_workspace: None,
+            show_details: true,
+            layout,
+            boards: HashSet::new(),
         })
     }
 
     pub async fn run(&mut self) -> Result<()> {
         let mut tui = Tui::new()?
             .tick_rate(self.tick_rate)
-            .frame_rate(self.frame_rate);
+            .frame_rate(self.frame_rate)
+            .mouse(self.config.config.mouse);
         tui.enter()?;
 
         for component in self.components.values_mut() {
@@ -137,6 +197,9 @@ impl App {
             Event::Render => action_tx.send(Action::Render)?,
             Event::Resize(x, y) => action_tx.send(Action::Resize(x, y))?,
             Event::Key(key) => self.handle_key_event(key)?,
+            Event::Mouse(mouse) if mouse.kind != MouseEventKind::Moved => {
+                self.handle_mouse_event(mouse)?
+            }
             Event::FocusGained => {
                 self.components
                     .get_mut(&self.focused)
@@ -155,6 +218,29 @@ impl App {
         Ok(())
     }
 
+    /// a popup or an edit keeps the mouse to itself, otherwise the panes check it is over them.
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        let action = Action::SendMouseEvent(mouse);
+        match self.focused {
+            ComponentId::Workspaces | ComponentId::Tasks | ComponentId::Board
+                if self.mode != Mode::Insert =>
+            {
+                for id in [ComponentId::Workspaces, self.tasks_view()] {
+                    self.components
+                        .get_mut(&id)
+                        .unwrap()
+                        .update(action.clone())?;
+                }
+            }
+            _ => self
+                .components
+                .get_mut(&self.focused)
+                .unwrap()
+                .update(action)?,
+        }
+        Ok(())
+    }
+
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
         // if editing mode send all keypresses to the focused component.
         if self.mode == Mode::Insert {
@@ -169,61 +255,44 @@ impl App {
             .config
             .keybindings
             .get(&Mode::Global)
-            .expect("did not find global keybindings")
-            .clone();
+            .expect("did not find global keybindings");
+        // the global keymap comes first, the keymap of the mode may not exist.
+        let keymaps: Vec<&HashMap<Vec<KeyEvent>, Action>> = [Some(global_keymap)]
+            .into_iter()
+            .chain([self.config.keybindings.get(&self.mode)])
+            .flatten()
+            .collect();
 
-        // use global keymap if action found return.
-        if self.use_keymap(key, &global_keymap)? {
-            return Ok(());
-        }
-
-        // search of the keymap of the focused component. if not found no action will be sent.
-        let Some(keymap) = self.config.keybindings.get(&self.mode) else {
-            return Ok(());
-        };
-
-        if self.use_keymap(key, &keymap.clone())? {
-            return Ok(());
+        let had_pending_keys = !self.pending_keys.0.is_empty();
+        let mut sequence = std::mem::take(&mut self.pending_keys.0);
+        sequence.push(key);
+        // the oldest keys are dropped until the sequence is bound or starts a longer binding.
+        while !sequence.is_empty() {
+            if let Some(action) = keymaps.iter().find_map(|keymap| keymap.get(&sequence)) {
+                self.action_tx.send(action.clone())?;
+                break;
+            }
+            let is_prefix = keymaps.iter().any(|keymap| {
+                keymap
+                    .keys()
+                    .any(|keys| keys.len() > sequence.len() && keys.starts_with(&sequence))
+            });
+            if is_prefix {
+                let timeout = Duration::from_millis(self.config.config.sequence_timeout);
+                self.pending_keys = (sequence.clone(), Some(Instant::now() + timeout));
+                self.action_tx.send(Action::PendingKeys(sequence))?;
+                return Ok(());
+            }
+            sequence.remove(0);
         }
 
-        if self.last_key_events.1.is_none() {
-            self.last_key_events.1 = Some(4);
+        self.pending_keys = (Vec::new(), None);
+        if had_pending_keys {
+            self.action_tx.send(Action::PendingKeys(Vec::new()))?;
         }
-        self.last_key_events.0.push(key);
-
         Ok(())
     }
 
-    fn use_keymap(
-        &mut self,
-        key: KeyEvent,
-        keymap: &HashMap<Vec<KeyEvent>, Action>,
-    ) -> Result<bool> {
-        let action_tx = self.action_tx.clone();
-
-        match keymap.get(&vec![key]) {
-            Some(action) => {
-                action_tx.send(action.clone())?;
-                return Ok(true);
-            }
-
-            _ => {
-                if self.last_key_events.1.is_none() {
-                    return Ok(false);
-                }
-                let mut events_list = self.last_key_events.0.clone();
-                events_list.push(key);
-                for i in 0..events_list.len() {
-                    if let Some(action) = keymap.get(&events_list[i..]) {
-                        action_tx.send(action.clone())?;
-                        return Ok(true);
-                    }
-                }
-            }
-        }
-        Ok(false)
-    }
-
     fn handle_actions(&mut self, tui: &mut Tui) -> Result<()> {
         while let Ok(action) = self.action_rx.try_recv() {
             if action != Action::Tick && action != Action::Render {
@@ -234,16 +303,16 @@ impl App {
             match target {
                 ComponentId::All => {
                     match action {
-                        Action::Tick => match self.last_key_events.1 {
-                            Some(0) => {
-                                self.last_key_events.0.clear();
-                                self.last_key_events.1 = None;
-                            }
-                            Some(i) => {
-                                self.last_key_events.1 = Some(i - 1);
-                            }
-                            None => {}
-                        },
+                        // the keys of a sequence that was not completed in time are dropped.
+                        Action::Tick
+                            if self
+                                .pending_keys
+                                .1
+                                .is_some_and(|deadline| deadline <= Instant::now()) =>
+                        {
+                            self.pending_keys = (Vec::new(), None);
+                            self.action_tx.send(Action::PendingKeys(Vec::new()))?;
+                        }
                         Action::Quit => self.should_quit = true,
                         Action::Suspend => self.should_suspend = true,
                         Action::Resume => self.should_suspend = false,
@@ -252,60 +321,200 @@ impl App {
                         Action::Render => self.render(tui)?,
                         Action::EnterInsertMode => self.mode = Mode::Insert,
                         Action::LeaveInsertMode => self.mode = Mode::Navigation,
-                        Action::FocusOnTasks => {
+                        Action::FocusOnTasks => self.focus_on(ComponentId::Tasks)?,
+                        Action::FocusOnWorkspaces => {
+                            if self.layout.hide_workspaces {
+                                self.layout.hide_workspaces = false;
+                                self.save_layout()?;
+                            }
+                            self.focus_on(ComponentId::Workspaces)?;
+                        }
+                        Action::OpenSortMenu => {
+                            if self.focused == ComponentId::SortMenu {
+                                self.action_tx.send(Action::Cancel)?;
+                                continue;
+                            }
                             self.components
                                 .get_mut(&self.focused)
                                 .unwrap()
                                 .focus(false)?;
-                            self.focused = ComponentId::Tasks;
+                            let sort_menu =
+                                self.components.get_mut(&ComponentId::SortMenu).unwrap();
+                            sort_menu.update(Action::SetupSortMenu(self.focused))?;
+                            self.focused = ComponentId::SortMenu;
+                            sort_menu.focus(true)?;
+                        }
+                        Action::ExitSortMenu(component_id) => self.focus_on(component_id)?,
+                        Action::OpenTaskSearch => {
+                            if self.focused == ComponentId::TaskSearch {
+                                continue;
+                            }
                             self.components
                                 .get_mut(&self.focused)
                                 .unwrap()
-                                .focus(true)?;
+                                .focus(false)?;
+                            let task_search =
+                                self.components.get_mut(&ComponentId::TaskSearch).unwrap();
+                            task_search.update(Action::SetupTaskSearch(self.focused))?;
+                            self.focused = ComponentId::TaskSearch;
+                            task_search.focus(true)?;
+                            self.mode = Mode::Insert;
                         }
-                        Action::FocusOnWorkspaces => {
+                        Action::ExitTaskSearch(component_id) => {
+                            self.focus_on(component_id)?;
+                            self.mode = Mode::Navigation;
+                        }
+                        Action::Help => {
+                            if self.focused == ComponentId::Help {
+                                continue;
+                            }
                             self.components
                                 .get_mut(&self.focused)
                                 .unwrap()
                                 .focus(false)?;
-                            self.focused = ComponentId::Workspaces;
+                            let help = self.components.get_mut(&ComponentId::Help).unwrap();
+                            help.update(Action::SetupHelp(self.focused))?;
+                            self.focused = ComponentId::Help;
+                            help.focus(true)?;
+                            self.mode = Mode::Insert;
+                        }
+                        Action::ExitHelp(component_id) => {
+                            self.focus_on(component_id)?;
+                            self.mode = Mode::Navigation;
+                        }
+                        Action::OpenTrash => {
+                            if self.focused == ComponentId::Trash {
+                                continue;
+                            }
                             self.components
                                 .get_mut(&self.focused)
                                 .unwrap()
-                                .focus(true)?;
+                                .focus(false)?;
+                            let trash = self.components.get_mut(&ComponentId::Trash).unwrap();
+                            trash.update(Action::SetupTrash(self.focused))?;
+                            self.focused = ComponentId::Trash;
+                            trash.focus(true)?;
+                            self.mode = Mode::Insert;
                         }
-                        Action::OpenSortMenu => {
-                            if self.focused == ComponentId::SortMenu {
-                                self.action_tx.send(Action::Cancel)?;
+                        Action::ExitTrash(component_id) => {
+                            self.focus_on(component_id)?;
+                            self.mode = Mode::Navigation;
+                        }
+                        Action::OpenTaskHistory => {
+                            if self.focused == ComponentId::TaskHistory {
                                 continue;
                             }
                             self.components
                                 .get_mut(&self.focused)
                                 .unwrap()
                                 .focus(false)?;
-                            let sort_menu =
-                                self.components.get_mut(&ComponentId::SortMenu).unwrap();
-                            sort_menu.update(Action::SetupSortMenu(self.focused))?;
-                            self.focused = ComponentId::SortMenu;
-                            sort_menu.focus(true)?;
+                            let history =
+                                self.components.get_mut(&ComponentId::TaskHistory).unwrap();
+                            history.update(Action::SetupTaskHistory(self.focused))?;
+                            self.focused = ComponentId::TaskHistory;
+                            history.focus(true)?;
+                            self.mode = Mode::Insert;
                         }
-                        Action::ExitSortMenu(component_id) => {
+                        Action::ExitTaskHistory(component_id) => {
+                            self.focus_on(component_id)?;
+                            self.mode = Mode::Navigation;
+                        }
+                        Action::OpenStatistics => {
+                            if self.focused == ComponentId::Statistics {
+                                continue;
+                            }
                             self.components
-                                .get_mut(&ComponentId::SortMenu)
+                                .get_mut(&self.focused)
                                 .unwrap()
                                 .focus(false)?;
-                            self.focused = component_id;
+                            let statistics =
+                                self.components.get_mut(&ComponentId::Statistics).unwrap();
+                            statistics.update(Action::SetupStatistics(self.focused))?;
+                            self.focused = ComponentId::Statistics;
+                            statistics.focus(true)?;
+                            self.mode = Mode::Insert;
+                        }
+                        Action::ExitStatistics(component_id) => {
+                            self.focus_on(component_id)?;
+                            self.mode = Mode::Navigation;
+                        }
+                        Action::AskConfirmation(..) => {
                             self.components
-                                .get_mut(&component_id)
+                                .get_mut(&self.focused)
                                 .unwrap()
-                                .focus(true)?;
+                                .focus(false)?;
+                            let confirmation =
+                                self.components.get_mut(&ComponentId::Confirmation).unwrap();
+                            confirmation.update(Action::SetupConfirmation(self.focused))?;
+                            self.focused = ComponentId::Confirmation;
+                            confirmation.focus(true)?;
+                            self.mode = Mode::Insert;
+                        }
+                        Action::ExitConfirmation(component_id) => {
+                            self.focus_on(component_id)?;
+                            self.mode = Mode::Navigation;
+                        }
+                        Action::OpenEditor(task_id, ref description) => {
+                            match self.edit_in_editor(tui, description) {
+                                Ok(edited) if edited != *description => {
+                                    self.action_tx.send(Action::UpdateTask(UpdateTask {
+                                        id: task_id,
+                                        description: Some(edited),
+                                        ..Default::default()
+                                    }))?;
+                                }
+                                Ok(_) => {}
+                                Err(e) => self.action_tx.send(Action::Error(format!(
+                                    "Could not edit the description: {e}"
+                                )))?,
+                            }
                         }
                         Action::SelectWorkspace(id) => {
+                            let board_shown = self.board_shown();
                             self.selected_workspace = Some(id);
                             self.action_tx.send(Action::RequestTasksData(id))?;
+                            self.refresh_view(board_shown)?;
                         }
                         Action::UnselectWorkspace => {
+                            let board_shown = self.board_shown();
                             self.selected_workspace = None;
+                            self.refresh_view(board_shown)?;
+                        }
+                        Action::ToggleBoard => {
+                            if let Some(id) = self.selected_workspace {
+                                let board_shown = self.board_shown();
+                                if !self.boards.remove(&id) {
+                                    self.boards.insert(id);
+                                }
+                                self.refresh_view(board_shown)?;
+                            }
+                        }
+                        Action::ToggleDetails => self.show_details = !self.show_details,
+                        Action::GrowWorkspaces | Action::ShrinkWorkspaces => {
+                            let steps = if action == Action::GrowWorkspaces {
+                                1
+                            } else {
+                                -1
+                            };
+                            self.layout.resize(steps, tui.size()?);
+                            self.save_layout()?;
+                        }
+                        Action::ToggleWorkspaces => {
+                            self.layout.hide_workspaces = !self.layout.hide_workspaces;
+                            if self.layout.hide_workspaces
+                                && self.focused == ComponentId::Workspaces
+                            {
+                                self.action_tx.send(Action::FocusOnTasks)?;
+                            }
+                            self.save_layout()?;
+                        }
+                        Action::CycleLayout => {
+                            self.layout.orientation = self.layout.orientation.next();
+                            self.action_tx.send(Action::Info(format!(
+                                "Layout: {}",
+                                self.layout.orientation
+                            )))?;
+                            self.save_layout()?;
                         }
                         _ => {}
                     }
@@ -327,9 +536,8 @@ impl App {
                         {
                             self.action_tx
                                 .send(Action::HighlightTask(name.to_string()))?;
-                        } else {
-                            return Err(e);
                         }
+                        self.action_tx.send(Action::Error(e.to_string()))?;
                     }
                     self.action_tx.send(Action::RequestTasksData(
                         self.selected_workspace.expect(
@@ -344,24 +552,37 @@ impl App {
                         {
                             self.action_tx
                                 .send(Action::HighlightWorkspace(name.to_string()))?;
-                        } else {
-                            return Err(e);
                         }
+                        self.action_tx.send(Action::Error(e.to_string()))?;
                     }
                     self.action_tx.send(Action::RequestWorkspacesData)?;
                 }
-                ComponentId::DatabaseGet => match action {
-                    Action::RequestTasksData(workspace_id) => {
-                        let tasks = self.database.get_tasks(workspace_id)?;
-                        self.action_tx
-                            .send(Action::NewTasksData((tasks, workspace_id)))?;
+                ComponentId::DatabaseHistory => {
+                    let (result, nothing_to_do) = match action {
+                        Action::Undo => (self.database.undo(), "Nothing to undo"),
+                        Action::Redo => (self.database.redo(), "Nothing to redo"),
+                        _ => continue,
+                    };
+                    match result {
+                        // the change can touch any workspace, reloading them reloads the tasks too.
+                        Ok(true) => self.action_tx.send(Action::RequestWorkspacesData)?,
+                        Ok(false) => self.action_tx.send(Action::Info(nothing_to_do.into()))?,
+                        Err(e) => self.action_tx.send(Action::Error(e.to_string()))?,
                     }
-                    Action::RequestWorkspacesData => {
-                        let workspaces = self.database.get_workspaces()?;
-                        self.action_tx.send(Action::NewWorkspacesData(workspaces))?;
+                }
+                ComponentId::DatabaseTrash => {
+                    if let Err(e) = self.database.handle_update_actions(action.clone()) {
+                        self.action_tx.send(Action::Error(e.to_string()))?;
                     }
-                    _ => {}
-                },
+                    self.action_tx.send(Action::RequestTrashData)?;
+                    // a restored item can be in any workspace, reloading them reloads the tasks too.
+                    self.action_tx.send(Action::RequestWorkspacesData)?;
+                }
+                ComponentId::DatabaseGet => {
+                    if let Err(e) = self.handle_database_get(action) {
+                        self.action_tx.send(Action::Error(e.to_string()))?;
+                    }
+                }
                 _ => {
                     if let Some(component) = self.components.get_mut(&target) {
                         component.update(action.clone())?;
@@ -374,6 +595,128 @@ impl App {
         Ok(())
     }
 
+    /// opens `text` in `$VISUAL` or `$EDITOR` with the interface suspended and returns what was
+    /// saved, the trailing newline added by most editors is dropped.
+    fn edit_in_editor(&mut self, tui: &mut Tui, text: &str) -> Result<String> {
+        let editor = env::var("VISUAL")
+            .or_else(|_| env::var("EDITOR"))
+            .unwrap_or_default();
+        let (program, args) = editor_command(&editor);
+        // a fresh file only the user can read, removed once it is dropped.
+        let mut file = tempfile::Builder::new()
+            .prefix(&format!("{}-", PROJECT_NAME.to_lowercase()))
+            .suffix(".md")
+            .tempfile()?;
+        file.write_all(text.as_bytes())?;
+        file.flush()?;
+
+        tui.exit()?;
+        let status = process::Command::new(program)
+            .args(args)
+            .arg(file.path())
+            .status();
+        tui.enter()?;
+        tui.terminal.clear()?;
+
+        let edited = match status {
+            Ok(status) if status.success() => fs::read_to_string(file.path()),
+            Ok(status) => Err(io::Error::other(format!("{program} exited with {status}"))),
+            Err(e) => Err(io::Error::new(e.kind(), format!("{program}: {e}"))),
+        };
+        Ok(trim_edited(&edited?).to_string())
+    }
+
+    fn handle_database_get(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::RequestTasksData(workspace_id) => {
+                let tasks = self.database.get_tasks(workspace_id)?;
+                self.action_tx
+                    .send(Action::NewTasksData((tasks, workspace_id)))?;
+            }
+            Action::RequestWorkspacesData => {
+                let workspaces = self.database.get_workspaces()?;
+                self.action_tx.send(Action::NewWorkspacesData(workspaces))?;
+            }
+            Action::SearchTasks(query) => {
+                let results = self.database.search_tasks(&query, SEARCH_RESULTS_LIMIT)?;
+                self.action_tx.send(Action::NewSearchResults(results))?;
+            }
+            Action::RequestTrashData => {
+                let items = self.database.get_trash()?;
+                self.action_tx.send(Action::NewTrashData(items))?;
+            }
+            Action::RequestTaskHistory(task_id) => {
+                let events = self.database.get_task_events(task_id)?;
+                self.action_tx.send(Action::NewTaskHistory(events))?;
+            }
+            Action::RequestStatistics => {
+                let stats = Stats::collect(
+                    &self.database,
+                    Local::now().naive_local(),
+                    STATS_DAYS.into(),
+                )?;
+                self.action_tx.send(Action::NewStatistics(stats))?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// keeps the layout for the next start, failing to do so is only reported.
+    fn save_layout(&self) -> Result<()> {
+        let path = self.config.config.data_dir.join(LAYOUT_FILE);
+        if let Err(e) = self.layout.save(&path) {
+            self.action_tx
+                .send(Action::Error(format!("Could not save the layout: {e}")))?;
+        }
+        Ok(())
+    }
+
+    fn board_shown(&self) -> bool {
+        self.selected_workspace
+            .is_some_and(|id| self.boards.contains(&id))
+    }
+
+    /// the component showing the tasks of the selected workspace, the table or the board.
+    fn tasks_view(&self) -> ComponentId {
+        if self.board_shown() {
+            ComponentId::Board
+        } else {
+            ComponentId::Tasks
+        }
+    }
+
+    /// moves the focus to `id`, the tasks are focused in the way they are shown.
+    fn focus_on(&mut self, id: ComponentId) -> Result<()> {
+        let id = match id {
+            ComponentId::Tasks | ComponentId::Board => self.tasks_view(),
+            id => id,
+        };
+        self.components
+            .get_mut(&self.focused)
+            .unwrap()
+            .focus(false)?;
+        self.focused = id;
+        self.components
+            .get_mut(&self.focused)
+            .unwrap()
+            .focus(true)?;
+        Ok(())
+    }
+
+    /// lets the table and the board know which one is shown when it changed since
+    /// `board_shown`, the focus follows.
+    fn refresh_view(&mut self, board_shown: bool) -> Result<()> {
+        if self.board_shown() == board_shown {
+            return Ok(());
+        }
+        self.action_tx.send(Action::ShowBoard(!board_shown))?;
+        if matches!(self.focused, ComponentId::Tasks | ComponentId::Board) {
+            self.focus_on(self.focused)?;
+        }
+        Ok(())
+    }
+
     fn handle_resize(&mut self, tui: &mut Tui, w: u16, h: u16) -> Result<()> {
         tui.resize(Rect::new(0, 0, w, h))?;
         self.render(tui)?;
@@ -381,14 +724,32 @@ impl App {
     }
 
     fn render(&mut self, tui: &mut Tui) -> Result<()> {
+        let tasks_view = self.tasks_view();
         tui.draw(|frame| {
             let area = frame.size();
-            let [workspace_area, task_area] =
-                Layout::horizontal([Constraint::Length(20), Constraint::Fill(1)]).areas(area);
+            let [main_area, status_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+            let [workspace_area, task_area] = self.layout.areas(main_area);
+            // the details go beside the tasks when there is room for both, under them otherwise.
+            let [task_area, details_area] = match (self.show_details, task_area.width >= 120) {
+                (false, _) => [task_area, Rect::default()],
+                (true, true) => {
+                    Layout::horizontal([Constraint::Fill(1), Constraint::Percentage(35)])
+                        .areas(task_area)
+                }
+                (true, false) => {
+                    Layout::vertical([Constraint::Fill(1), Constraint::Percentage(40)])
+                        .areas(task_area)
+                }
+            };
+            let status_bar = self.components.get_mut(&ComponentId::StatusBar).unwrap();
+            let _ = status_bar.update(Action::SetupStatusBar(self.mode, self.focused));
             for (id, component) in &mut self.components {
                 let area = match id {
-                    ComponentId::Workspaces => workspace_area,
-                    ComponentId::Tasks => task_area,
+                    ComponentId::Workspaces if !self.layout.hide_workspaces => workspace_area,
+                    ComponentId::Tasks | ComponentId::Board if *id == tasks_view => task_area,
+                    ComponentId::StatusBar => status_area,
+                    ComponentId::TaskDetails if self.show_details => details_area,
                     _ => continue,
                 };
 
@@ -403,7 +764,55 @@ impl App {
             // let _ = fps.draw(frame, area);
             let sort_menu = self.components.get_mut(&ComponentId::SortMenu).unwrap();
             let _ = sort_menu.draw(frame, area);
+            let task_search = self.components.get_mut(&ComponentId::TaskSearch).unwrap();
+            let _ = task_search.draw(frame, area);
+            let help = self.components.get_mut(&ComponentId::Help).unwrap();
+            let _ = help.draw(frame, area);
+            let trash = self.components.get_mut(&ComponentId::Trash).unwrap();
+            let _ = trash.draw(frame, area);
+            let history = self.components.get_mut(&ComponentId::TaskHistory).unwrap();
+            let _ = history.draw(frame, area);
+            let statistics = self.components.get_mut(&ComponentId::Statistics).unwrap();
+            let _ = statistics.draw(frame, area);
+            let confirmation = self.components.get_mut(&ComponentId::Confirmation).unwrap();
+            let _ = confirmation.draw(frame, area);
+            let which_key = self.components.get_mut(&ComponentId::WhichKey).unwrap();
+            let _ = which_key.draw(frame, main_area);
+            let notifications = self
+                .components
+                .get_mut(&ComponentId::Notifications)
+                .unwrap();
+            let _ = notifications.draw(frame, area);
         })?;
         Ok(())
     }
 }
+
+/// the program of the `editor` command and its arguments, `code --wait` for instance, vi
+/// without any.
+fn editor_command(editor: &str) -> (&str, Vec<&str>) {
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    (program, words.collect())
+}
+
+/// the text saved by an editor without the trailing newline most of them add.
+fn trim_edited(text: &str) -> &str {
+    text.trim_end_matches(['\n', '\r'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_editor_command() {
+        assert_eq!(editor_command("nvim"), ("nvim", vec![]));
+        assert_eq!(editor_command("  code --wait "), ("code", vec!["--wait"]));
+        assert_eq!(editor_command(""), ("vi", vec![]));
+        assert_eq!(trim_edited("buy milk\n"), "buy milk");
+        assert_eq!(trim_edited("buy milk\r\n\n"), "buy milk");
+        assert_eq!(trim_edited("  indented\n\nlines"), "  indented\n\nlines");
+    }
+}
@@ -1,6 +1,12 @@
-use clap::Parser;
+use chrono::NaiveDateTime;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 use crate::config::{get_config_dir, get_data_dir};
+use crate::database_ops::transfer::{ConflictPolicy, ImportMode};
+use crate::formats::OutputFormat;
+use crate::stats::STATS_DAYS;
+use crate::structs::{parse_date_input, parse_priority, parse_tag, Recurrence, TaskEventKind};
 
 #[derive(Parser, Debug)]
 #[command(author, version = version(), about)]
@@ -12,6 +18,242 @@ pub struct Cli {
     /// Frame rate, i.e. number of frames per second
     #[arg(short, long, value_name = "FLOAT", default_value_t = 60.0)]
     pub frame_rate: f64,
+
+    /// Run a single command instead of starting the interface
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Add a task at the end of a workspace
+    Add {
+        /// Name of the workspace
+        workspace: String,
+        /// Name of the task
+        #[arg(value_parser = name_parser)]
+        task: String,
+        /// Priority from A (highest) to D (lowest)
+        #[arg(short, long, value_parser = priority_parser)]
+        priority: Option<i32>,
+        /// Description of the task
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Due date, YYYY-MM-DD or "YYYY-MM-DD HH:MM"
+        #[arg(long, value_parser = date_parser)]
+        due: Option<NaiveDateTime>,
+        /// Start date, YYYY-MM-DD or "YYYY-MM-DD HH:MM"
+        #[arg(long, value_parser = date_parser)]
+        start: Option<NaiveDateTime>,
+        /// Tag of the task, can be repeated
+        #[arg(short, long = "tag", value_parser = tag_parser)]
+        tags: Vec<String>,
+        /// Add the task as the last subtask of this task, by id or name
+        #[arg(long)]
+        parent: Option<String>,
+        /// Make the task recur: daily, "weekly mon,fri", monthly or "every N days"
+        #[arg(short, long, value_parser = recurrence_parser)]
+        repeat: Option<Recurrence>,
+    },
+    /// List the tasks of a workspace, or of every workspace
+    List {
+        /// Name of the workspace
+        workspace: Option<String>,
+        /// Only list the tasks having this tag, can be repeated
+        #[arg(short, long = "tag", value_parser = tag_parser)]
+        tags: Vec<String>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Mark a task as done
+    Done {
+        /// Id or name of the task
+        task: String,
+        /// Workspace to look for the task name in
+        #[arg(short, long)]
+        workspace: Option<String>,
+        /// Mark the task as not done instead, a recurring task is rescheduled when done
+        #[arg(long)]
+        undo: bool,
+    },
+    /// Move a task and its subtasks to the trash
+    Rm {
+        /// Id or name of the task
+        task: String,
+        /// Workspace to look for the task name in
+        #[arg(short, long)]
+        workspace: Option<String>,
+    },
+    /// Edit a task
+    Edit {
+        /// Id or name of the task
+        task: String,
+        /// Workspace to look for the task name in
+        #[arg(short, long)]
+        workspace: Option<String>,
+        /// New name of the task
+        #[arg(short, long, value_parser = name_parser)]
+        name: Option<String>,
+        /// New description of the task
+        #[arg(short, long)]
+        description: Option<String>,
+        /// New priority from A (highest) to D (lowest)
+        #[arg(short, long, value_parser = priority_parser)]
+        priority: Option<i32>,
+        /// New due date, YYYY-MM-DD or "YYYY-MM-DD HH:MM"
+        #[arg(long, value_parser = date_parser, conflicts_with = "clear_due")]
+        due: Option<NaiveDateTime>,
+        /// New start date, YYYY-MM-DD or "YYYY-MM-DD HH:MM"
+        #[arg(long, value_parser = date_parser, conflicts_with = "clear_start")]
+        start: Option<NaiveDateTime>,
+        /// Remove the due date
+        #[arg(long)]
+        clear_due: bool,
+        /// Remove the start date
+        #[arg(long)]
+        clear_start: bool,
+        /// Make the task recur: daily, "weekly mon,fri", monthly or "every N days"
+        #[arg(short, long, value_parser = recurrence_parser, conflicts_with = "no_repeat")]
+        repeat: Option<Recurrence>,
+        /// Stop the task from recurring
+        #[arg(long)]
+        no_repeat: bool,
+    },
+    /// Search the names and descriptions of the tasks of every workspace
+    Search {
+        /// Words to look for, the last one may be incomplete
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// Maximum number of results
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Show what happened to the tasks, the last change first
+    Log {
+        /// Only show the changes since this date, YYYY-MM-DD or "YYYY-MM-DD HH:MM"
+        #[arg(short, long, value_parser = date_parser)]
+        since: Option<NaiveDateTime>,
+        /// Only show the changes of the tasks of this workspace
+        #[arg(short, long)]
+        workspace: Option<String>,
+        /// Only show this kind of change, can be repeated
+        #[arg(short, long = "kind", value_enum)]
+        kinds: Vec<TaskEventKind>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Show how many tasks are open and done, and how many got done lately
+    Stats {
+        /// Number of days to count the completed tasks over
+        #[arg(short, long, default_value_t = STATS_DAYS, value_parser = clap::value_parser!(u16).range(1..))]
+        days: u16,
+        /// Output format, CSV only holds the workspaces
+        #[arg(short, long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Manage workspaces
+    #[command(subcommand)]
+    Workspace(WorkspaceCommand),
+    /// Restore or purge the removed tasks and workspaces
+    #[command(subcommand)]
+    Trash(TrashCommand),
+    /// Write every workspace and task to a JSON document
+    Export {
+        /// File to write to, defaults to the standard output
+        file: Option<PathBuf>,
+    },
+    /// Restore workspaces and tasks from a JSON document written by export
+    Import {
+        /// File to read from, `-` reads the standard input
+        file: PathBuf,
+        /// Whether to keep the existing workspaces and tasks
+        #[arg(short, long, value_enum, default_value_t)]
+        mode: ImportMode,
+        /// What to do with tasks that already exist in their workspace
+        #[arg(long, value_enum, default_value_t)]
+        on_conflict: ConflictPolicy,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WorkspaceCommand {
+    /// Add a workspace at the end of the list
+    Add {
+        /// Name of the workspace
+        #[arg(value_parser = name_parser)]
+        name: String,
+    },
+    /// Move a workspace and all of its tasks to the trash
+    Rm {
+        /// Name of the workspace
+        name: String,
+    },
+    /// Rename a workspace
+    Rename {
+        /// Current name of the workspace
+        name: String,
+        /// New name of the workspace
+        #[arg(value_parser = name_parser)]
+        new_name: String,
+    },
+    /// List the workspaces
+    List {
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TrashCommand {
+    /// List the removed tasks and workspaces, the last removed first
+    List {
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Put a removed task or workspace back where it was
+    Restore {
+        /// Id of the trash entry
+        id: i32,
+    },
+    /// Remove entries from the trash for good
+    Purge {
+        /// Id of the trash entry, every entry is purged when not given
+        id: Option<i32>,
+    },
+}
+
+fn priority_parser(input: &str) -> Result<i32, String> {
+    parse_priority(input).ok_or_else(|| format!("invalid priority `{input}`, expected A-D or 1-4"))
+}
+
+/// a name without its surrounding spaces, it can not be empty.
+fn name_parser(input: &str) -> Result<String, String> {
+    match input.trim() {
+        "" => Err("the name can not be empty".to_string()),
+        name => Ok(name.to_string()),
+    }
+}
+
+fn tag_parser(input: &str) -> Result<String, String> {
+    parse_tag(&format!("#{}", input.trim_start_matches('#')))
+        .ok_or_else(|| format!("invalid tag `{input}`"))
+}
+
+fn recurrence_parser(input: &str) -> Result<Recurrence, String> {
+    input.parse()
+}
+
+fn date_parser(input: &str) -> Result<NaiveDateTime, String> {
+    parse_date_input(input).ok_or_else(|| {
+        format!("invalid date `{input}`, expected YYYY-MM-DD or \"YYYY-MM-DD HH:MM\"")
+    })
 }
 
 const VERSION_MESSAGE: &str = concat!(
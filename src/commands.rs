@@ -0,0 +1,424 @@
+use crate::{
+    cli::{Command, TrashCommand, WorkspaceCommand},
+    database_ops::DatabaseOperations,
+    errors::DoMeError,
+    formats::*,
+    stats::Stats,
+    structs::*,
+};
+use chrono::Local;
+use color_eyre::Result;
+use std::{
+    fs::File,
+    io::{stdin, stdout, BufReader, BufWriter, Write},
+};
+
+/// Runs a command from the command line against the database without starting the interface.
+pub fn run(command: Command, db: &DatabaseOperations) -> Result<()> {
+    match command {
+        Command::Add {
+            workspace,
+            task,
+            priority,
+            description,
+            due,
+            start,
+            tags,
+            parent,
+            repeat,
+        } => {
+            let workspace_id = find_workspace(db, &workspace)?;
+            let parent_id = match parent {
+                Some(parent) => Some(find_task(db, &parent, Some(&workspace))?.id),
+                None => None,
+            };
+            db.handle_add_task(AddTask {
+                name: task.trim().to_string(),
+                description,
+                priority,
+                due_date: due,
+                start_date: start,
+                workspace_id,
+                tags: normalize_tags(tags),
+                parent_id,
+                recurrence: repeat,
+                ..Default::default()
+            })?;
+            // printing the id lets scripts refer to the new task.
+            if let Some(id) = db.search_task_name(task.trim(), workspace_id)? {
+                println!("{id}");
+            }
+        }
+        Command::List {
+            workspace,
+            tags,
+            format,
+        } => {
+            let mut workspaces = db.get_workspaces()?;
+            WorkspaceSorter::default().sort(&mut workspaces);
+            if let Some(name) = workspace {
+                workspaces.retain(|w| w.name == name);
+                if workspaces.is_empty() {
+                    return Err(DoMeError::WorkspaceNotFound(name).into());
+                }
+            }
+            let mut records = Vec::new();
+            for workspace in workspaces {
+                let mut tasks = db.get_tasks(workspace.id)?;
+                TaskSorter::default().sort(&mut tasks);
+                records.extend(
+                    task_tree(&tasks)
+                        .into_iter()
+                        .filter(|(_, task)| tags.iter().all(|tag| task.tags.contains(tag)))
+                        .map(|(depth, task)| TaskRecord::new(&workspace, task).with_depth(depth)),
+                );
+            }
+            write_tasks(&mut stdout().lock(), &records, format)?;
+        }
+        Command::Done {
+            task,
+            workspace,
+            undo,
+        } => {
+            let task = find_task(db, &task, workspace.as_deref())?;
+            let update = if undo || task.completed {
+                UpdateTask {
+                    id: task.id,
+                    completed: Some(!undo),
+                    ..Default::default()
+                }
+            } else {
+                task.toggle_completion(Local::now().naive_local())
+            };
+            db.handle_update_task(update)?;
+        }
+        Command::Rm { task, workspace } => {
+            let task = find_task(db, &task, workspace.as_deref())?;
+            db.trash_task(task.id)?;
+        }
+        Command::Edit {
+            task,
+            workspace,
+            name,
+            description,
+            priority,
+            due,
+            start,
+            clear_due,
+            clear_start,
+            repeat,
+            no_repeat,
+        } => {
+            let task = find_task(db, &task, workspace.as_deref())?;
+            db.handle_update_task(UpdateTask {
+                id: task.id,
+                name: name.map(|name| name.trim().to_string()),
+                description,
+                priority,
+                due_date: if clear_due { Some(None) } else { due.map(Some) },
+                start_date: if clear_start {
+                    Some(None)
+                } else {
+                    start.map(Some)
+                },
+                recurrence: if no_repeat {
+                    Some(None)
+                } else {
+                    repeat.map(Some)
+                },
+                ..Default::default()
+            })?;
+        }
+        Command::Search {
+            query,
+            limit,
+            format,
+        } => {
+            let results = db.search_tasks(&query.join(" "), limit)?;
+            let records: Vec<SearchRecord> = results.iter().map(SearchRecord::new).collect();
+            write_search_results(&mut stdout().lock(), &records, format)?;
+        }
+        Command::Log {
+            since,
+            workspace,
+            kinds,
+            format,
+        } => {
+            if let Some(name) = &workspace {
+                find_workspace(db, name)?;
+            }
+            let records: Vec<EventRecord> = db
+                .get_events_since(since)?
+                .iter()
+                .filter(|event| workspace.is_none() || event.workspace == workspace)
+                .filter(|event| kinds.is_empty() || kinds.contains(&event.kind))
+                .map(EventRecord::new)
+                .collect();
+            write_events(&mut stdout().lock(), &records, format)?;
+        }
+        Command::Stats { days, format } => {
+            let stats = Stats::collect(db, Local::now().naive_local(), days.into())?;
+            write_stats(&mut stdout().lock(), &StatsRecord::new(&stats), format)?;
+        }
+        Command::Workspace(command) => run_workspace_command(command, db)?,
+        Command::Trash(command) => run_trash_command(command, db)?,
+        Command::Export { file } => {
+            let document = db.export()?;
+            match file {
+                Some(path) => {
+                    let mut writer = BufWriter::new(File::create(path)?);
+                    serde_json::to_writer_pretty(&mut writer, &document)?;
+                    writer.flush()?;
+                }
+                None => {
+                    serde_json::to_writer_pretty(stdout().lock(), &document)?;
+                    println!();
+                }
+            }
+        }
+        Command::Import {
+            file,
+            mode,
+            on_conflict,
+        } => {
+            let document: ExportDocument = if file.as_os_str() == "-" {
+                serde_json::from_reader(stdin().lock())?
+            } else {
+                serde_json::from_reader(BufReader::new(File::open(file)?))?
+            };
+            let summary = db.import(document, mode, on_conflict)?;
+            println!(
+                "imported {} workspaces and {} tasks, skipped {} and overwrote {} existing tasks",
+                summary.workspaces, summary.tasks, summary.skipped, summary.overwritten
+            );
+        }
+    }
+    Ok(())
+}
+
+fn run_workspace_command(command: WorkspaceCommand, db: &DatabaseOperations) -> Result<()> {
+    match command {
+        WorkspaceCommand::Add { name } => {
+            db.handle_add_workspace(AddWorkspace {
+                name: name.trim().to_string(),
+                ..Default::default()
+            })?;
+        }
+        WorkspaceCommand::Rm { name } => {
+            let id = find_workspace(db, &name)?;
+            db.trash_workspace(id)?;
+        }
+        WorkspaceCommand::Rename { name, new_name } => {
+            let id = find_workspace(db, &name)?;
+            db.handle_update_workspace(UpdateWorkspace {
+                id,
+                name: Some(new_name.trim().to_string()),
+                ..Default::default()
+            })?;
+        }
+        WorkspaceCommand::List { format } => {
+            let mut workspaces = db.get_workspaces()?;
+            WorkspaceSorter::default().sort(&mut workspaces);
+            let mut records = Vec::new();
+            for workspace in workspaces {
+                let tasks = db.get_tasks(workspace.id)?;
+                records.push(WorkspaceRecord::new(&workspace, &tasks));
+            }
+            write_workspaces(&mut stdout().lock(), &records, format)?;
+        }
+    }
+    Ok(())
+}
+
+fn run_trash_command(command: TrashCommand, db: &DatabaseOperations) -> Result<()> {
+    match command {
+        TrashCommand::List { format } => {
+            let records: Vec<TrashRecord> = db.get_trash()?.iter().map(TrashRecord::new).collect();
+            write_trash(&mut stdout().lock(), &records, format)?;
+        }
+        TrashCommand::Restore { id } => {
+            let item = db
+                .get_trash_item(id)?
+                .ok_or(DoMeError::TrashItemNotFound(id))?;
+            db.restore_trash_item(&item)?;
+        }
+        TrashCommand::Purge { id: Some(id) } => {
+            let item = db
+                .get_trash_item(id)?
+                .ok_or(DoMeError::TrashItemNotFound(id))?;
+            db.purge_trash_items(&[item])?;
+        }
+        TrashCommand::Purge { id: None } => db.purge_trash_items(&db.get_trash()?)?,
+    }
+    Ok(())
+}
+
+fn find_workspace(db: &DatabaseOperations, name: &str) -> Result<i32> {
+    db.search_workspace_name(name)?
+        .ok_or_else(|| DoMeError::WorkspaceNotFound(name.to_string()).into())
+}
+
+/// finds a task by id, or by name in the given workspace or in all of them.
+fn find_task(db: &DatabaseOperations, reference: &str, workspace: Option<&str>) -> Result<Task> {
+    let workspace_ids = match workspace {
+        Some(name) => vec![find_workspace(db, name)?],
+        None => db.get_workspaces()?.iter().map(|w| w.id).collect(),
+    };
+
+    if let Ok(id) = reference.parse::<i32>() {
+        if let Some(task) = db.get_task(id)? {
+            if workspace_ids.contains(&task.workspace_id) {
+                return Ok(task);
+            }
+        }
+    }
+
+    let mut found = Vec::new();
+    for workspace_id in workspace_ids {
+        if let Some(id) = db.search_task_name(reference, workspace_id)? {
+            found.push(id);
+        }
+    }
+    match found[..] {
+        [id] => db
+            .get_task(id)?
+            .ok_or_else(|| DoMeError::TaskNotFound(reference.to_string()).into()),
+        [] => Err(DoMeError::TaskNotFound(reference.to_string()).into()),
+        _ => Err(DoMeError::AmbiguousTask(reference.to_string()).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn setup() -> DatabaseOperations {
+        let db = DatabaseOperations::new(":memory:".into());
+        for workspace in ["work", "home"] {
+            run_workspace_command(
+                WorkspaceCommand::Add {
+                    name: workspace.into(),
+                },
+                &db,
+            )
+            .unwrap();
+        }
+        for (workspace, task) in [("work", "report"), ("work", "review"), ("home", "review")] {
+            run(
+                Command::Add {
+                    workspace: workspace.into(),
+                    task: task.into(),
+                    priority: parse_priority("b"),
+                    description: None,
+                    due: None,
+                    start: None,
+                    tags: Vec::new(),
+                    parent: None,
+                    repeat: None,
+                },
+                &db,
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_empty_names_are_rejected() {
+        use crate::cli::Cli;
+        use clap::Parser;
+
+        for args in [
+            &["do-me", "add", "work", ""][..],
+            &["do-me", "add", "work", "   "],
+            &["do-me", "edit", "report", "--name", " "],
+            &["do-me", "workspace", "rename", "work", ""],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err(), "{args:?}");
+        }
+        let cli = Cli::try_parse_from(["do-me", "add", "work", " report "]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Add { task, .. }) if task == "report"));
+    }
+
+    #[test]
+    fn test_find_task() {
+        let db = setup();
+        let report = find_task(&db, "report", None).unwrap();
+        assert_eq!(report.priority, 2);
+        assert_eq!(
+            find_task(&db, &report.id.to_string(), None).unwrap(),
+            report
+        );
+        assert!(find_task(&db, &report.id.to_string(), Some("home")).is_err());
+
+        let error = find_task(&db, "review", None).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<DoMeError>(),
+            Some(DoMeError::AmbiguousTask(_))
+        ));
+        let review = find_task(&db, "review", Some("home")).unwrap();
+        assert_eq!(review.workspace_id, find_workspace(&db, "home").unwrap());
+
+        let error = find_task(&db, "missing", None).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<DoMeError>(),
+            Some(DoMeError::TaskNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_edit_commands() {
+        let db = setup();
+        run(
+            Command::Done {
+                task: "report".into(),
+                workspace: None,
+                undo: false,
+            },
+            &db,
+        )
+        .unwrap();
+        run(
+            Command::Edit {
+                task: "report".into(),
+                workspace: None,
+                name: Some("final report".into()),
+                description: Some("for the board".into()),
+                priority: None,
+                due: parse_date_input("2024-05-01"),
+                start: None,
+                clear_due: false,
+                clear_start: false,
+                repeat: None,
+                no_repeat: false,
+            },
+            &db,
+        )
+        .unwrap();
+        let task = find_task(&db, "final report", None).unwrap();
+        assert!(task.completed);
+        assert_eq!(task.description, "for the board");
+        assert_eq!(task.due_date, parse_date_input("2024-05-01"));
+
+        run(
+            Command::Rm {
+                task: "review".into(),
+                workspace: Some("work".into()),
+            },
+            &db,
+        )
+        .unwrap();
+        run_workspace_command(
+            WorkspaceCommand::Rename {
+                name: "home".into(),
+                new_name: "house".into(),
+            },
+            &db,
+        )
+        .unwrap();
+        assert_eq!(find_task(&db, "review", None).unwrap().name, "review");
+        assert!(find_workspace(&db, "home").is_err());
+        assert!(find_workspace(&db, "house").is_ok());
+    }
+}
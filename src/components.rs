@@ -1,12 +1,28 @@
 use color_eyre::Result;
-use ratatui::{layout::Rect, Frame};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    Frame,
+};
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{action::Action, config::Config};
 
+pub mod board;
+pub mod confirmation;
 pub mod fps;
+pub mod help;
+pub mod notifications;
+pub mod search;
 pub mod sort_menu;
+pub mod statistics;
+pub mod status_bar;
+pub mod task_details;
+pub mod task_history;
+pub mod task_search;
 pub mod tasks;
+pub mod text_input;
+pub mod trash;
+pub mod which_key;
 pub mod workspaces;
 
 /// `Component` is a trait that represents a visual and interactive element of the user interface.
@@ -91,3 +107,20 @@ pub trait Component {
         Ok(())
     }
 }
+
+/// helper function to create a centered rect using up certain percentage of the available rect `r`
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::vertical([
+        Constraint::Percentage((100 - percent_y) / 2),
+        Constraint::Percentage(percent_y),
+        Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .split(r);
+
+    Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(popup_layout[1])[1]
+}
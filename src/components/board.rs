@@ -0,0 +1,489 @@
+use super::Component;
+use crate::{
+    action::Action,
+    config::{Config, Statuses, StyleName},
+    structs::*,
+};
+use chrono::{Local, NaiveDateTime};
+use color_eyre::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{prelude::*, widgets::*};
+use std::collections::HashMap;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// what the columns of the board stand for.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+enum Grouping {
+    /// one column per status of the configuration.
+    #[default]
+    Status,
+    Priority,
+}
+
+impl Grouping {
+    fn titles(self, statuses: &Statuses) -> Vec<String> {
+        match self {
+            Grouping::Status => statuses.iter().map(|s| s.name.clone()).collect(),
+            Grouping::Priority => (1..=4)
+                .map(|priority| format!("Priority {}", priority_letter(priority)))
+                .collect(),
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Grouping::Status => Grouping::Priority,
+            Grouping::Priority => Grouping::Status,
+        }
+    }
+
+    fn column_of(self, task: &Task, statuses: &Statuses) -> usize {
+        match self {
+            Grouping::Status => statuses.rank(task),
+            Grouping::Priority => task.priority.clamp(1, 4) as usize - 1,
+        }
+    }
+
+    /// the changes putting `task` in `column`.
+    fn move_to(
+        self,
+        task: &Task,
+        column: usize,
+        statuses: &Statuses,
+        now: NaiveDateTime,
+    ) -> UpdateTask {
+        match self {
+            Grouping::Status => {
+                let status = &statuses[column];
+                task.set_status(&status.name, status.done, now)
+            }
+            Grouping::Priority => UpdateTask {
+                id: task.id,
+                priority: Some(column as i32 + 1),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// The tasks of a workspace as cards in columns by status or by priority.
+#[derive(Default, Debug)]
+pub struct Board {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    is_focused: bool,
+    /// whether the board is shown in place of the tasks table.
+    shown: bool,
+    grouping: Grouping,
+    selected_workspace: Option<i32>,
+    tasks: Vec<Task>, // every task of the workspace, in the order of the tree.
+    columns: Vec<Vec<usize>>, // the indices in `tasks` of the cards of each column.
+    column: usize,
+    states: Vec<ListState>,
+    to_be_selected: Option<i32>, // the id of the task to select once reloaded.
+    shown_task: Option<Task>,    // the selected task as last sent to the details pane.
+    area: Rect,
+    dragged: Option<i32>, // the id of the card held down by the mouse.
+}
+
+impl Board {
+    pub fn new() -> Self {
+        let mut board = Self::default();
+        board.refresh_columns();
+        board
+    }
+
+    fn selected(&self) -> Option<&Task> {
+        let row = self.states.get(self.column)?.selected()?;
+        Some(&self.tasks[*self.columns[self.column].get(row)?])
+    }
+
+    /// puts the tasks in their column, keeping the selected card selected.
+    fn refresh_columns(&mut self) {
+        let selected = self.to_be_selected.take().or(self.selected().map(|t| t.id));
+        let statuses = &self.config.statuses;
+        let count = self.grouping.titles(statuses).len().max(1);
+        self.columns = vec![Vec::new(); count];
+        for (index, task) in self.tasks.iter().enumerate() {
+            let column = self.grouping.column_of(task, statuses).min(count - 1);
+            self.columns[column].push(index);
+        }
+        self.states.resize_with(count, ListState::default);
+        self.column = self.column.min(count - 1);
+        for (column, state) in self.states.iter_mut().enumerate() {
+            let len = self.columns[column].len();
+            match state.selected() {
+                _ if len == 0 => state.select(None),
+                Some(row) => state.select(Some(row.min(len - 1))),
+                None => state.select(Some(0)),
+            }
+        }
+        let found = selected.and_then(|id| {
+            self.columns.iter().enumerate().find_map(|(column, cards)| {
+                let row = cards.iter().position(|&index| self.tasks[index].id == id)?;
+                Some((column, row))
+            })
+        });
+        if let Some((column, row)) = found {
+            self.column = column;
+            self.states[column].select(Some(row));
+        }
+    }
+
+    /// lets the other components know when the selected task or its data changed.
+    fn notify_selection(&mut self) -> Result<()> {
+        if !self.shown {
+            return Ok(());
+        }
+        let selected = self.selected().cloned();
+        if selected != self.shown_task {
+            self.shown_task.clone_from(&selected);
+            self.command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::SelectTask(selected))?;
+        }
+        Ok(())
+    }
+
+    fn select_row(&mut self, forward: bool) {
+        let len = self.columns[self.column].len();
+        if len == 0 {
+            return;
+        }
+        let state = &mut self.states[self.column];
+        let row = match state.selected() {
+            Some(row) if forward => (row + 1) % len,
+            Some(row) => (row + len - 1) % len,
+            None => 0,
+        };
+        state.select(Some(row));
+    }
+
+    /// moves the selected card to `column`, it stays selected.
+    fn move_card(&mut self, column: usize) -> Result<()> {
+        let Some(task) = self.selected() else {
+            return Ok(());
+        };
+        if column >= self.columns.len() || column == self.column {
+            return Ok(());
+        }
+        let t = self.grouping.move_to(
+            task,
+            column,
+            &self.config.statuses,
+            Local::now().naive_local(),
+        );
+        self.to_be_selected = Some(task.id);
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::UpdateTask(t))?;
+        Ok(())
+    }
+
+    /// swaps the selected card with the closest one of its siblings above or below it in the
+    /// column.
+    fn reorder_card(&mut self, down: bool) -> Result<()> {
+        let (Some(task), Some(row)) = (self.selected(), self.states[self.column].selected()) else {
+            return Ok(());
+        };
+        let cards = &self.columns[self.column];
+        let sibling = |&&index: &&usize| self.tasks[index].parent_id == task.parent_id;
+        let other = if down {
+            cards[row + 1..].iter().find(sibling)
+        } else {
+            cards[..row].iter().rev().find(sibling)
+        };
+        if let Some(&other) = other {
+            let t = UpdateTask {
+                id: task.id,
+                order: Some(self.tasks[other].order),
+                ..Default::default()
+            };
+            self.to_be_selected = Some(task.id);
+            self.command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::UpdateTask(t))?;
+        }
+        Ok(())
+    }
+
+    /// the column and card at a position of the screen.
+    fn card_at(&self, column: u16, row: u16) -> Option<(usize, Option<usize>)> {
+        let areas = self.column_areas(self.area);
+        let index = areas
+            .iter()
+            .position(|area| area.contains(Position::new(column, row)))?;
+        let area = areas[index];
+        // the cards start under the border.
+        let card = (row > area.y && row + 1 < area.bottom())
+            .then(|| (row - area.y - 1) as usize + self.states[index].offset())
+            .filter(|&card| card < self.columns[index].len());
+        Some((index, card))
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        let command_tx = self.command_tx.as_ref().unwrap().clone();
+        let Some((column, card)) = self.card_at(mouse.column, mouse.row) else {
+            self.dragged = None;
+            return Ok(());
+        };
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if !self.is_focused {
+                    command_tx.send(Action::FocusOnTasks)?;
+                }
+                self.column = column;
+                if card.is_some() {
+                    self.states[column].select(card);
+                    self.dragged = self.selected().map(|t| t.id);
+                }
+            }
+            // a card dropped on another column moves there.
+            MouseEventKind::Up(MouseButton::Left) if self.dragged.take().is_some() => {
+                self.move_card(column)?;
+            }
+            MouseEventKind::ScrollDown => {
+                self.column = column;
+                self.select_row(true);
+            }
+            MouseEventKind::ScrollUp => {
+                self.column = column;
+                self.select_row(false);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn column_areas(&self, area: Rect) -> Vec<Rect> {
+        let count = self.columns.len() as u32;
+        Layout::horizontal((0..count).map(|_| Constraint::Ratio(1, count)))
+            .split(area)
+            .to_vec()
+    }
+}
+
+/// a card showing the priority or the status, the one its column does not stand for.
+fn card<'a>(
+    config: &Config,
+    grouping: Grouping,
+    task: &'a Task,
+    now: NaiveDateTime,
+) -> ListItem<'a> {
+    let priority_style = [
+        StyleName::Priority1,
+        StyleName::Priority2,
+        StyleName::Priority3,
+        StyleName::Priority4,
+    ][task.priority.clamp(1, 4) as usize - 1];
+    let name_style = if task.completed {
+        config.styles[&StyleName::Completed]
+    } else if task.is_overdue(now) {
+        config.styles[&StyleName::Overdue]
+    } else {
+        Style::default()
+    };
+    let mut spans = Vec::new();
+    match grouping {
+        Grouping::Status => spans.push(Span::styled(
+            format!("{} ", priority_letter(task.priority)),
+            config.styles[&priority_style],
+        )),
+        Grouping::Priority => {
+            if let Some(status) = config.statuses.of(task) {
+                spans.push(Span::styled(format!("{} ", status.glyph), status.style));
+            }
+        }
+    }
+    spans.push(Span::styled(task.name.as_str(), name_style));
+    for tag in &task.tags {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            format!(" {tag} "),
+            config.styles[&StyleName::Tag],
+        ));
+    }
+    ListItem::new(Line::from(spans))
+}
+
+/// the tasks of a workspace with every task followed by its subtasks.
+fn tree_order(tasks: Vec<Task>) -> Vec<Task> {
+    let mut children: HashMap<Option<i32>, Vec<Task>> = HashMap::new();
+    for task in tasks {
+        children.entry(task.parent_id).or_default().push(task);
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|t| t.order);
+    }
+    let mut ordered = Vec::new();
+    let mut stack: Vec<Task> = children.remove(&None).unwrap_or_default();
+    stack.reverse();
+    while let Some(task) = stack.pop() {
+        if let Some(subtasks) = children.remove(&Some(task.id)) {
+            stack.extend(subtasks.into_iter().rev());
+        }
+        ordered.push(task);
+    }
+    ordered
+}
+
+impl Component for Board {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        self.refresh_columns();
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn focus(&mut self, focus: bool) -> Result<()> {
+        self.is_focused = focus;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        let command_tx = self.command_tx.as_ref().unwrap().clone();
+        match action {
+            Action::NewTasksData((tasks, workspace_id)) => {
+                if self.selected_workspace != Some(workspace_id) {
+                    self.selected_workspace = Some(workspace_id);
+                    self.column = 0;
+                    self.states.clear();
+                }
+                self.tasks = tree_order(tasks);
+                self.refresh_columns();
+            }
+            Action::UnselectWorkspace => {
+                self.selected_workspace = None;
+                self.tasks.clear();
+                self.refresh_columns();
+            }
+            Action::JumpToTask(task) => self.to_be_selected = Some(task.id),
+            Action::ShowBoard(shown) => {
+                self.shown = shown;
+                self.shown_task = None;
+            }
+            Action::ToggleBoardColumns => {
+                self.grouping = self.grouping.next();
+                self.refresh_columns();
+            }
+            Action::GoUp => self.select_row(false),
+            Action::GoDown => self.select_row(true),
+            Action::GoToTop if !self.columns[self.column].is_empty() => {
+                self.states[self.column].select(Some(0));
+            }
+            Action::GoToBottom if !self.columns[self.column].is_empty() => {
+                self.states[self.column].select(Some(self.columns[self.column].len() - 1));
+            }
+            Action::GoLeft if self.column == 0 => command_tx.send(Action::FocusOnWorkspaces)?,
+            Action::GoLeft => self.column -= 1,
+            Action::GoRight => self.column = (self.column + 1).min(self.columns.len() - 1),
+            Action::MoveItemLeft if self.column > 0 => self.move_card(self.column - 1)?,
+            Action::MoveItemRight => self.move_card(self.column + 1)?,
+            Action::MoveItemUp => self.reorder_card(false)?,
+            Action::MoveItemDown => self.reorder_card(true)?,
+            Action::ToggleCompletion => {
+                if let Some(task) = self.selected() {
+                    let t = task.toggle_completion(Local::now().naive_local());
+                    self.to_be_selected = Some(task.id);
+                    command_tx.send(Action::UpdateTask(t))?;
+                }
+            }
+            Action::DeleteItem => {
+                if let Some(task) = self.selected() {
+                    command_tx.send(Action::RemoveTask(task.id))?;
+                }
+            }
+            Action::SendMouseEvent(mouse) => self.handle_mouse_event(mouse)?,
+            _ => {}
+        }
+        self.notify_selection()?;
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        self.area = area;
+        let block_style = if self.is_focused {
+            self.config.styles[&StyleName::Highlight]
+        } else {
+            Style::default()
+        };
+        if self.selected_workspace.is_none() {
+            let block = Block::default()
+                .title("Board")
+                .border_style(block_style)
+                .border_type(BorderType::Thick)
+                .borders(Borders::ALL);
+            let paragraph = Paragraph::new("No workspace selected")
+                .block(block)
+                .alignment(Alignment::Center);
+            frame.render_widget(paragraph, area);
+            return Ok(());
+        }
+
+        let now = Local::now().naive_local();
+        let titles = self.grouping.titles(&self.config.statuses);
+        for (column, column_area) in self.column_areas(area).into_iter().enumerate() {
+            let cards: Vec<ListItem> = self.columns[column]
+                .iter()
+                .map(|&index| card(&self.config, self.grouping, &self.tasks[index], now))
+                .collect();
+            // only the selected column shows its selection.
+            let (border_style, selected_style) = if column == self.column {
+                (block_style, self.config.styles[&StyleName::Selected])
+            } else {
+                (Style::default(), Style::default())
+            };
+            let block = Block::default()
+                .title(format!("{} ({})", titles[column], cards.len()))
+                .border_style(border_style)
+                .border_type(BorderType::Thick)
+                .borders(Borders::ALL);
+            let list = List::new(cards)
+                .block(block)
+                .highlight_style(selected_style);
+            frame.render_stateful_widget(list, column_area, &mut self.states[column]);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_status_columns() {
+        let statuses = Config::fallback().statuses;
+        let now = NaiveDate::from_ymd_opt(2024, 5, 6)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let mut task = Task {
+            id: 1,
+            priority: 3,
+            ..Default::default()
+        };
+        assert_eq!(Grouping::Status.column_of(&task, &statuses), 0);
+        task.status = Some("blocked".into());
+        assert_eq!(Grouping::Status.column_of(&task, &statuses), 2);
+        assert_eq!(Grouping::Priority.column_of(&task, &statuses), 2);
+
+        // a task dropped in a done column is completed.
+        let t = Grouping::Status.move_to(&task, 4, &statuses, now);
+        assert_eq!(t.completed, Some(true));
+        assert_eq!(t.status, Some(Some("cancelled".into())));
+        task.completed = true;
+        let t = Grouping::Status.move_to(&task, 1, &statuses, now);
+        assert_eq!(t.completed, Some(false));
+        let t = Grouping::Priority.move_to(&task, 0, &statuses, now);
+        assert_eq!(t.priority, Some(1));
+    }
+}
@@ -0,0 +1,123 @@
+use super::Component;
+use crate::action::Action;
+use crate::app::ComponentId;
+use crate::config::{Config, StyleName};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Popup asking whether an action should go through, the action is only sent on `y`.
+#[derive(Debug)]
+pub struct Confirmation {
+    is_focused: bool,
+    objective: ComponentId,
+    message: String,
+    action: Option<Action>,
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+}
+
+impl Confirmation {
+    pub fn new() -> Self {
+        Self {
+            is_focused: false,
+            objective: ComponentId::Workspaces,
+            message: String::new(),
+            action: None,
+            command_tx: None,
+            config: Config::default(),
+        }
+    }
+
+    fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<()> {
+        let command_tx = self.command_tx.as_ref().unwrap();
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                if let Some(action) = self.action.take() {
+                    command_tx.send(action)?;
+                }
+                command_tx.send(Action::ExitConfirmation(self.objective))?;
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') | KeyCode::Esc => {
+                command_tx.send(Action::ExitConfirmation(self.objective))?;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+}
+
+impl Component for Confirmation {
+    fn focus(&mut self, focus: bool) -> Result<()> {
+        self.is_focused = focus;
+        if !focus {
+            self.action = None;
+        }
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::SetupConfirmation(component_id) => {
+                self.objective = component_id;
+            }
+            Action::AskConfirmation(message, action) => {
+                self.message = message;
+                self.action = Some(*action);
+            }
+            Action::SendKeyEvent(key) => {
+                self.handle_insert_mode(key)?;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.is_focused {
+            return Ok(());
+        }
+        let block_style = self.config.styles[&StyleName::Highlight];
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title("Confirm")
+            .title_bottom(Line::from(" y yes · n no ").right_aligned())
+            .style(block_style);
+
+        // as wide as the message allows, the message is wrapped past that.
+        let width = (self.message.chars().count() as u16 + 4)
+            .clamp(30, 60)
+            .min(area.width);
+        let lines = self
+            .message
+            .chars()
+            .count()
+            .div_ceil(width.saturating_sub(4).max(1) as usize)
+            .max(1);
+        let height = (lines as u16 + 2).min(area.height);
+        let area = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        frame.render_widget(Clear, area); //this clears out the background
+        let message = Paragraph::new(self.message.as_str())
+            .style(Style::default())
+            .wrap(Wrap { trim: true })
+            .block(block.padding(Padding::horizontal(1)));
+        frame.render_widget(message, area);
+        Ok(())
+    }
+}
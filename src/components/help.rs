@@ -0,0 +1,281 @@
+use super::{
+    centered_rect,
+    search::{highlight_matches, is_match},
+    text_input::{InputResponse, TextInput},
+    Component,
+};
+use crate::action::Action;
+use crate::app::{ComponentId, Mode};
+use crate::config::{key_sequence_to_string, Config, KeyBindings, StyleName};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use std::collections::BTreeMap;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// the modes in the order they are listed.
+const MODES: [Mode; 3] = [Mode::Global, Mode::Navigation, Mode::Insert];
+
+/// An action and every key sequence bound to it in a mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HelpEntry {
+    mode: Mode,
+    keys: String,
+    action: String,
+}
+
+/// Popup listing the configured keybindings, typing filters them.
+#[derive(Debug)]
+pub struct Help {
+    is_focused: bool,
+    objective: ComponentId,
+    input: TextInput,
+    entries: Vec<HelpEntry>,
+    scroll: usize,
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+}
+
+impl Help {
+    pub fn new() -> Self {
+        Self {
+            is_focused: false,
+            objective: ComponentId::Workspaces,
+            input: TextInput::default(),
+            entries: Vec::new(),
+            scroll: 0,
+            command_tx: None,
+            config: Config::default(),
+        }
+    }
+
+    fn filtered_entries(&self) -> Vec<&HelpEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| {
+                self.input.is_empty()
+                    || is_match(&entry.keys, self.input.value())
+                    || is_match(&entry.action, self.input.value())
+            })
+            .collect()
+    }
+
+    fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<()> {
+        let command_tx = self.command_tx.as_ref().unwrap();
+        // the key opening the help closes it while nothing is typed.
+        if key.code == KeyCode::Char('?') && self.input.is_empty() {
+            command_tx.send(Action::ExitHelp(self.objective))?;
+            return Ok(());
+        }
+        match self.input.handle_key(key) {
+            InputResponse::Edited => self.scroll = 0,
+            InputResponse::Handled => {}
+            InputResponse::Ignored => match key.code {
+                KeyCode::Esc if self.input.is_empty() => {
+                    command_tx.send(Action::ExitHelp(self.objective))?;
+                }
+                KeyCode::Esc => {
+                    self.input.clear();
+                    self.scroll = 0;
+                }
+                KeyCode::Down => self.scroll += 1,
+                KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::PageDown => self.scroll += 10,
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                _ => {}
+            },
+        };
+        Ok(())
+    }
+}
+
+/// the bindings of every mode grouped by action, the actions are sorted by name.
+fn help_entries(keybindings: &KeyBindings) -> Vec<HelpEntry> {
+    let mut entries = Vec::new();
+    for mode in MODES {
+        let Some(bindings) = keybindings.get(&mode) else {
+            continue;
+        };
+        let mut actions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (keys, action) in bindings {
+            actions
+                .entry(action_name(action))
+                .or_default()
+                .push(key_sequence_to_string(keys));
+        }
+        for (action, mut keys) in actions {
+            keys.sort();
+            entries.push(HelpEntry {
+                mode,
+                keys: keys.join(" "),
+                action,
+            });
+        }
+    }
+    entries
+}
+
+/// the name of an action in words, `MoveItemDown` is `Move item down`.
+pub(super) fn action_name(action: &Action) -> String {
+    let mut name = String::new();
+    for c in action.to_string().chars() {
+        if c.is_uppercase() && !name.is_empty() {
+            name.push(' ');
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+impl Component for Help {
+    fn focus(&mut self, focus: bool) -> Result<()> {
+        self.is_focused = focus;
+        if !focus {
+            self.input.clear();
+            self.scroll = 0;
+        }
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.input.set_vim_mode(config.config.vim_input);
+        self.entries = help_entries(&config.keybindings);
+        self.config = config;
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::SetupHelp(component_id) => {
+                self.objective = component_id;
+            }
+            Action::SendKeyEvent(key) => {
+                self.handle_insert_mode(key)?;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.is_focused {
+            return Ok(());
+        }
+        let block_style = self.config.styles[&StyleName::Highlight];
+        let match_style = self.config.styles[&StyleName::SearchMatch];
+
+        let area = centered_rect(70, 80, area);
+        frame.render_widget(Clear, area); //this clears out the background
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title("Keybindings")
+            .style(block_style);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [input_area, bindings_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        let (typed, cursor) = self.input.visible(input_area.width.saturating_sub(1));
+        let input = Paragraph::new(Line::from([vec![Span::raw("/")], typed.spans].concat()))
+            .block(Block::default().borders(Borders::BOTTOM))
+            .style(Style::default());
+        frame.render_widget(input, input_area);
+
+        let entries = self.filtered_entries();
+        let keys_width = entries
+            .iter()
+            .map(|entry| entry.keys.chars().count())
+            .max()
+            .unwrap_or_default();
+        let mut lines = Vec::new();
+        for mode in MODES {
+            let mut mode_entries = entries.iter().filter(|entry| entry.mode == mode).peekable();
+            if mode_entries.peek().is_none() {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::styled(
+                format!("{mode:?}"),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+            for entry in mode_entries {
+                let mut spans = vec![Span::raw("  ")];
+                spans.extend(highlight_matches(
+                    &entry.keys,
+                    self.input.value(),
+                    match_style,
+                ));
+                spans.push(Span::raw(
+                    " ".repeat(keys_width - entry.keys.chars().count() + 2),
+                ));
+                spans.extend(highlight_matches(
+                    &entry.action,
+                    self.input.value(),
+                    match_style,
+                ));
+                lines.push(Line::from(spans));
+            }
+        }
+        if lines.is_empty() {
+            lines.push(Line::raw("No keybinding found"));
+        }
+
+        self.scroll = self.scroll.min(lines.len().saturating_sub(1));
+        let bindings = Paragraph::new(lines)
+            .style(Style::default())
+            .scroll((self.scroll as u16, 0));
+        frame.render_widget(bindings, bindings_area);
+
+        frame.set_cursor(input_area.x + 1 + cursor, input_area.y);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::parse_key_sequence;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_help_entries() {
+        let mut keybindings = KeyBindings::default();
+        keybindings.insert(
+            Mode::Navigation,
+            HashMap::from([
+                (parse_key_sequence("<j>").unwrap(), Action::GoDown),
+                (parse_key_sequence("<down>").unwrap(), Action::GoDown),
+                (parse_key_sequence("<g><g>").unwrap(), Action::GoToBottom),
+            ]),
+        );
+        keybindings.insert(
+            Mode::Global,
+            HashMap::from([(parse_key_sequence("<ctrl-c>").unwrap(), Action::Quit)]),
+        );
+
+        let entry = |mode, keys: &str, action: &str| HelpEntry {
+            mode,
+            keys: keys.into(),
+            action: action.into(),
+        };
+        assert_eq!(
+            help_entries(&keybindings),
+            vec![
+                entry(Mode::Global, "<ctrl-c>", "Quit"),
+                entry(Mode::Navigation, "<down> <j>", "Go down"),
+                entry(Mode::Navigation, "<g><g>", "Go to bottom"),
+            ]
+        );
+    }
+}
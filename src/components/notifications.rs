@@ -0,0 +1,110 @@
+use super::Component;
+use crate::action::Action;
+use crate::config::{Config, StyleName};
+use color_eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+use std::time::{Duration, Instant};
+
+/// how long the notifications stay on screen, errors stay longer so they can be read.
+const INFO_DURATION: Duration = Duration::from_secs(3);
+const ERROR_DURATION: Duration = Duration::from_secs(6);
+/// maximum number of notifications shown at once, the oldest ones are dropped.
+const MAX_NOTIFICATIONS: usize = 3;
+const WIDTH: u16 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+struct Notification {
+    severity: Severity,
+    message: String,
+    expires_at: Instant,
+}
+
+/// Toasts shown in the top right corner for the `Info` and `Error` actions.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    notifications: Vec<Notification>,
+    config: Config,
+}
+
+impl Notifications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, severity: Severity, message: String) {
+        let duration = match severity {
+            Severity::Info => INFO_DURATION,
+            Severity::Error => ERROR_DURATION,
+        };
+        if self.notifications.len() == MAX_NOTIFICATIONS {
+            self.notifications.remove(0);
+        }
+        self.notifications.push(Notification {
+            severity,
+            message,
+            expires_at: Instant::now() + duration,
+        });
+    }
+}
+
+impl Component for Notifications {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::Info(message) => self.push(Severity::Info, message),
+            Action::Error(message) => self.push(Severity::Error, message),
+            Action::Tick => {
+                let now = Instant::now();
+                self.notifications.retain(|n| n.expires_at > now);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let width = WIDTH.min(area.width);
+        let mut y = area.y;
+        for notification in &self.notifications {
+            let (title, style) = match notification.severity {
+                Severity::Info => ("Info", self.config.styles[&StyleName::Info]),
+                Severity::Error => ("Error", self.config.styles[&StyleName::Error]),
+            };
+            // the message is wrapped inside the borders.
+            let text_width = width.saturating_sub(2).max(1) as usize;
+            let lines = notification
+                .message
+                .chars()
+                .count()
+                .div_ceil(text_width)
+                .max(1);
+            let height = (lines as u16 + 2).min(area.bottom().saturating_sub(y));
+            if height < 3 {
+                break;
+            }
+            let toast_area = Rect::new(area.right() - width, y, width, height);
+            frame.render_widget(Clear, toast_area); //this clears out the background
+            let toast = Paragraph::new(notification.message.as_str())
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::bordered()
+                        .border_type(BorderType::Rounded)
+                        .title(title)
+                        .style(style),
+                );
+            frame.render_widget(toast, toast_area);
+            y += height;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use ratatui::{style::Style, text::Span};
+use std::ops::Range;
+
+/// Finds the byte ranges of `text` that match `query`, ignoring case.
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let mut matched = 0;
+        let mut end = start;
+        while matched < query.len() && end < chars.len() {
+            let lower: Vec<char> = chars[end].1.to_lowercase().collect();
+            if !query[matched..].starts_with(&lower) {
+                break;
+            }
+            matched += lower.len();
+            end += 1;
+        }
+        if matched == query.len() {
+            let end_byte = chars.get(end).map_or(text.len(), |(i, _)| *i);
+            matches.push(chars[start].0..end_byte);
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+pub fn is_match(text: &str, query: &str) -> bool {
+    !find_matches(text, query).is_empty()
+}
+
+/// Splits `text` into spans where the parts matching `query` use `match_style`.
+pub fn highlight_matches(text: &str, query: &str, match_style: Style) -> Vec<Span<'static>> {
+    highlight_ranges(text, find_matches(text, query), match_style)
+}
+
+/// Splits `text` into spans where the parts matching any of the words of `query` use
+/// `match_style`.
+pub fn highlight_words(text: &str, query: &str, match_style: Style) -> Vec<Span<'static>> {
+    let mut ranges: Vec<Range<usize>> = query
+        .split_whitespace()
+        .flat_map(|word| find_matches(text, word))
+        .collect();
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    highlight_ranges(text, merged, match_style)
+}
+
+fn highlight_ranges(
+    text: &str,
+    ranges: Vec<Range<usize>>,
+    match_style: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for range in ranges {
+        if range.start > last {
+            spans.push(Span::raw(text[last..range.start].to_string()));
+        }
+        spans.push(Span::styled(text[range.clone()].to_string(), match_style));
+        last = range.end;
+    }
+    if last < text.len() || spans.is_empty() {
+        spans.push(Span::raw(text[last..].to_string()));
+    }
+    spans
+}
+
+/// Finds the next index in `matches` after `current`, wrapping around, or before it when
+/// `backward` is set.
+pub fn next_match(matches: &[bool], current: Option<usize>, backward: bool) -> Option<usize> {
+    let len = matches.len();
+    if len == 0 {
+        return None;
+    }
+    let current = current.unwrap_or(if backward { 0 } else { len - 1 });
+    (1..=len)
+        .map(|step| {
+            if backward {
+                (current + len - step % len) % len
+            } else {
+                (current + step) % len
+            }
+        })
+        .find(|&index| matches[index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_matches() {
+        assert_eq!(
+            find_matches("Review the review", "review"),
+            vec![0..6, 11..17]
+        );
+        assert_eq!(find_matches("aaa", "aa"), vec![0..2]);
+        assert_eq!(find_matches("café CAFÉ", "é"), vec![3..5, 9..11]);
+        assert!(find_matches("anything", "").is_empty());
+        assert!(find_matches("short", "longer query").is_empty());
+    }
+
+    #[test]
+    fn test_highlight_matches() {
+        let style = Style::default();
+        let spans = highlight_matches("write the report", "the", style);
+        let parts: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(parts, vec!["write ", "the", " report"]);
+
+        let spans = highlight_words("write the report", "rep the report", style);
+        let parts: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(parts, vec!["write ", "the", " ", "report"]);
+    }
+
+    #[test]
+    fn test_next_match() {
+        let matches = [false, true, false, true];
+        assert_eq!(next_match(&matches, Some(1), false), Some(3));
+        assert_eq!(next_match(&matches, Some(3), false), Some(1));
+        assert_eq!(next_match(&matches, Some(1), true), Some(3));
+        assert_eq!(next_match(&matches, Some(3), true), Some(1));
+        assert_eq!(next_match(&matches, None, false), Some(1));
+        assert_eq!(next_match(&[false, false], Some(0), false), None);
+    }
+}
@@ -1,18 +1,21 @@
-use super::Component;
+use super::{centered_rect, Component};
 use crate::action::Action;
 use crate::app::ComponentId;
 use crate::config::{Config, StyleName};
 use crate::structs::{TaskSortType, TaskSorter, WorkspaceSortType, WorkspaceSorter};
 use color_eyre::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{prelude::*, widgets::*};
 use tokio::sync::mpsc::UnboundedSender;
 
 /// Sorting options
 const WORKSPACE_OPTIONS: [&str; 3] = ["Name", "Date created", "Last Updated"];
-const TASK_OPTIONS: [&str; 5] = [
+const TASK_OPTIONS: [&str; 7] = [
     "Name",
     "Completion",
+    "Status",
     "Date created",
+    "Due date",
     "Priority",
     "Description",
 ];
@@ -23,10 +26,12 @@ const WORKSPACE_SORTERS: [WorkspaceSortType; 3] = [
     WorkspaceSortType::UpdateDate,
 ];
 
-const TASK_SORTERS: [TaskSortType; 5] = [
+const TASK_SORTERS: [TaskSortType; 7] = [
     TaskSortType::Name,
     TaskSortType::Completion,
+    TaskSortType::Status,
     TaskSortType::CreateDate,
+    TaskSortType::DueDate,
     TaskSortType::Priority,
     TaskSortType::Description,
 ];
@@ -37,6 +42,8 @@ pub struct SortMenu {
     objective: ComponentId,
     list: OptionList<'static>,
     desc: bool,
+    /// where the menu was last drawn, to find the clicked option.
+    area: Rect,
     command_tx: Option<UnboundedSender<Action>>,
     config: Config,
 }
@@ -55,6 +62,7 @@ impl SortMenu {
             objective: ComponentId::Workspaces,
             list: OptionList::default(),
             desc: false,
+            area: Rect::default(),
             config: Config::default(),
         }
     }
@@ -99,6 +107,32 @@ impl SortMenu {
         self.list.state.select(Some(self.list.items.len() - 1));
     }
 
+    /// a click on an option sorts by it, a click outside closes the menu.
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        let command_tx = self.command_tx.as_ref().unwrap();
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if !self.area.contains(Position::new(mouse.column, mouse.row)) {
+                    command_tx.send(Action::Cancel)?;
+                    return Ok(());
+                }
+                let index =
+                    mouse.row.saturating_sub(self.area.y + 1) as usize + self.list.state.offset();
+                if mouse.row > self.area.y
+                    && mouse.row + 1 < self.area.bottom()
+                    && index < self.list.items.len()
+                {
+                    self.list.state.select(Some(index));
+                    command_tx.send(Action::Select)?;
+                }
+            }
+            MouseEventKind::ScrollDown => self.select_next(),
+            MouseEventKind::ScrollUp => self.select_previous(),
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn select_top(&mut self) {
         if self.list.items.is_empty() {
             return;
@@ -107,23 +141,6 @@ impl SortMenu {
     }
 }
 
-/// helper function to create a centered rect using up certain percentage of the available rect `r`
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::vertical([
-        Constraint::Percentage((100 - percent_y) / 2),
-        Constraint::Percentage(percent_y),
-        Constraint::Percentage((100 - percent_y) / 2),
-    ])
-    .split(r);
-
-    Layout::horizontal([
-        Constraint::Percentage((100 - percent_x) / 2),
-        Constraint::Percentage(percent_x),
-        Constraint::Percentage((100 - percent_x) / 2),
-    ])
-    .split(popup_layout[1])[1]
-}
-
 impl Component for SortMenu {
     fn focus(&mut self, focus: bool) -> Result<()> {
         self.is_focused = focus;
@@ -167,7 +184,7 @@ impl Component for SortMenu {
                             WorkspaceSorter::new(WORKSPACE_SORTERS[selected].clone(), self.desc);
                         command_tx.send(Action::SortWorkspaces(sorter))?;
                     }
-                    ComponentId::Tasks => {
+                    ComponentId::Tasks | ComponentId::Board => {
                         let sorter = TaskSorter::new(TASK_SORTERS[selected].clone(), self.desc);
                         command_tx.send(Action::SortTasks(sorter))?;
                     }
@@ -182,10 +199,13 @@ impl Component for SortMenu {
             Action::ToggleSortDirection => {
                 self.desc = !self.desc;
             }
+            Action::SendMouseEvent(mouse) => {
+                self.handle_mouse_event(mouse)?;
+            }
             Action::SetupSortMenu(component_id) => {
                 self.objective = component_id;
                 match component_id {
-                    ComponentId::Tasks => {
+                    ComponentId::Tasks | ComponentId::Board => {
                         self.list.items = TASK_OPTIONS.to_vec();
                     }
                     ComponentId::Workspaces => {
@@ -229,6 +249,7 @@ impl Component for SortMenu {
                 .highlight_spacing(HighlightSpacing::Always);
 
             let area = centered_rect(60, 20, area);
+            self.area = area;
             frame.render_widget(Clear, area); //this clears out the background
             frame.render_stateful_widget(items, area, &mut self.list.state);
         }
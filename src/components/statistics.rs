@@ -0,0 +1,274 @@
+use super::{centered_rect, Component};
+use crate::action::Action;
+use crate::app::ComponentId;
+use crate::config::{Config, StyleName};
+use crate::stats::Stats;
+use crate::structs::{format_date, priority_letter};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// width of the bar drawn for the completion rate of a workspace.
+const RATE_WIDTH: usize = 10;
+
+/// Popup showing how many tasks are open and done, and how many got done lately.
+#[derive(Debug)]
+pub struct Statistics {
+    is_focused: bool,
+    objective: ComponentId,
+    stats: Option<Stats>,
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+}
+
+impl Statistics {
+    pub fn new() -> Self {
+        Self {
+            is_focused: false,
+            objective: ComponentId::Workspaces,
+            stats: None,
+            command_tx: None,
+            config: Config::default(),
+        }
+    }
+
+    fn priority_style(&self, priority: i32) -> Style {
+        let name = [
+            StyleName::Priority1,
+            StyleName::Priority2,
+            StyleName::Priority3,
+            StyleName::Priority4,
+        ][priority.clamp(1, 4) as usize - 1];
+        self.config.styles[&name]
+    }
+
+    fn workspaces(&self, stats: &Stats) -> Table<'static> {
+        let completed_style = self.config.styles[&StyleName::Completed];
+        let total = (stats.workspaces.len() > 1).then(|| stats.total());
+        let rows = stats
+            .workspaces
+            .iter()
+            .chain(total.as_ref())
+            .map(|workspace| {
+                let rate = match workspace.completion_rate() {
+                    Some(rate) => {
+                        let filled = (rate * RATE_WIDTH as f64).round() as usize;
+                        Line::from(vec![
+                            Span::styled("█".repeat(filled), completed_style),
+                            Span::styled(
+                                "░".repeat(RATE_WIDTH - filled),
+                                Style::default().add_modifier(Modifier::DIM),
+                            ),
+                            Span::raw(format!(" {:.0}%", rate * 100.0)),
+                        ])
+                    }
+                    None => Line::raw("-"),
+                };
+                Row::new(vec![
+                    Cell::from(workspace.name.clone()),
+                    Cell::from(Text::raw(workspace.open.to_string()).right_aligned()),
+                    Cell::from(Text::raw(workspace.done.to_string()).right_aligned()),
+                    Cell::from(rate),
+                ])
+            });
+        let name_width = stats
+            .workspaces
+            .iter()
+            .map(|workspace| workspace.name.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max(9);
+        Table::new(
+            rows,
+            [
+                Constraint::Length(name_width as u16),
+                Constraint::Length(5),
+                Constraint::Length(5),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(vec!["Workspace", " Open", " Done", "Completion"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+    }
+
+    fn draw_completions(&self, frame: &mut Frame, stats: &Stats, area: Rect) {
+        let block = Block::default().borders(Borders::TOP).title(format!(
+            "Completed in the last {} days: {}",
+            stats.completed_per_day.len(),
+            stats.completed()
+        ));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        // a narrow screen shows the last days only.
+        let days = &stats.completed_per_day[stats
+            .completed_per_day
+            .len()
+            .saturating_sub(inner.width as usize)..];
+        let width = days.len() as u16;
+        let [chart_area, dates_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+        let counts: Vec<u64> = days.iter().map(|(_, count)| *count as u64).collect();
+        frame.render_widget(
+            Sparkline::default()
+                .data(&counts)
+                .style(self.config.styles[&StyleName::Completed]),
+            Rect {
+                width,
+                ..chart_area
+            },
+        );
+        if let (Some((first, _)), Some((last, _))) = (days.first(), days.last()) {
+            let dates = Rect {
+                width,
+                ..dates_area
+            };
+            let dim = Style::default().add_modifier(Modifier::DIM);
+            frame.render_widget(
+                Paragraph::new(first.format("%m-%d").to_string()).style(dim),
+                dates,
+            );
+            frame.render_widget(
+                Paragraph::new(last.format("%m-%d").to_string())
+                    .style(dim)
+                    .alignment(Alignment::Right),
+                dates,
+            );
+        }
+    }
+
+    fn priorities(&self, stats: &Stats) -> BarChart<'static> {
+        let bars: Vec<Bar> = (1..=4)
+            .zip(stats.priorities)
+            .map(|(priority, open)| {
+                Bar::default()
+                    .value(open as u64)
+                    .label(priority_letter(priority).to_string().into())
+                    .style(self.priority_style(priority))
+            })
+            .collect();
+        BarChart::default()
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .title("Open by priority"),
+            )
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(3)
+            .bar_gap(2)
+    }
+
+    fn oldest_open(&self, stats: &Stats) -> List<'static> {
+        let items: Vec<ListItem> = stats
+            .oldest_open
+            .iter()
+            .map(|open| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", format_date(&open.task.create_date)),
+                        Style::default().add_modifier(Modifier::DIM),
+                    ),
+                    Span::styled(
+                        format!("{} ", priority_letter(open.task.priority)),
+                        self.priority_style(open.task.priority),
+                    ),
+                    Span::styled(
+                        format!("{} ", open.task.name),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!("in {}", open.workspace)),
+                ]))
+            })
+            .collect();
+        List::new(items).block(
+            Block::default()
+                .borders(Borders::TOP)
+                .title("Oldest open tasks"),
+        )
+    }
+
+    fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<()> {
+        if let KeyCode::Esc | KeyCode::Char('q') = key.code {
+            self.command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::ExitStatistics(self.objective))?;
+        }
+        Ok(())
+    }
+}
+
+impl Component for Statistics {
+    fn focus(&mut self, focus: bool) -> Result<()> {
+        self.is_focused = focus;
+        if focus {
+            self.command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::RequestStatistics)?;
+        }
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::SetupStatistics(component_id) => self.objective = component_id,
+            Action::NewStatistics(stats) => self.stats = Some(stats),
+            Action::SendKeyEvent(key) => {
+                self.handle_insert_mode(key)?;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.is_focused {
+            return Ok(());
+        }
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title("Statistics")
+            .title_bottom(Line::from(" esc close ").right_aligned())
+            .style(self.config.styles[&StyleName::Highlight]);
+
+        let area = centered_rect(80, 80, area);
+        frame.render_widget(Clear, area); //this clears out the background
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let Some(stats) = &self.stats else {
+            return Ok(());
+        };
+
+        let rows = stats.workspaces.len() + usize::from(stats.workspaces.len() > 1);
+        let [workspaces_area, completions_area, bottom_area] = Layout::vertical([
+            Constraint::Max(rows as u16 + 2),
+            Constraint::Length(5),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+        let [priorities_area, oldest_area] =
+            Layout::horizontal([Constraint::Length(20), Constraint::Fill(1)])
+                .spacing(2)
+                .areas(bottom_area);
+
+        frame.render_widget(self.workspaces(stats), workspaces_area);
+        self.draw_completions(frame, stats, completions_area);
+        frame.render_widget(self.priorities(stats), priorities_area);
+        frame.render_widget(self.oldest_open(stats), oldest_area);
+        Ok(())
+    }
+}
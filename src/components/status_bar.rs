@@ -0,0 +1,117 @@
+use super::Component;
+use crate::action::Action;
+use crate::app::{ComponentId, Mode};
+use crate::config::{key_sequence_to_string, Config, StyleName};
+use crate::structs::{Task, TaskSorter, Workspace};
+use color_eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::{prelude::*, widgets::*};
+use std::collections::HashMap;
+
+/// Bottom line showing the state of the app: mode, focus, workspace, counts and the latest message.
+#[derive(Debug, Default)]
+pub struct StatusBar {
+    mode: Mode,
+    focused: ComponentId,
+    workspaces: Vec<Workspace>,
+    selected_workspace: Option<i32>,
+    tasks: Vec<Task>,
+    /// the last sort applied to each workspace.
+    sorters: HashMap<i32, TaskSorter>,
+    pending: Vec<KeyEvent>,
+    message: Option<(StyleName, String)>,
+    config: Config,
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn workspace_name(&self) -> Option<&str> {
+        let id = self.selected_workspace?;
+        self.workspaces
+            .iter()
+            .find(|workspace| workspace.id == id)
+            .map(|workspace| workspace.name.as_str())
+    }
+}
+
+impl Component for StatusBar {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::SetupStatusBar(mode, focused) => {
+                self.mode = mode;
+                self.focused = focused;
+            }
+            Action::NewWorkspacesData(workspaces) => self.workspaces = workspaces,
+            Action::SelectWorkspace(id) => self.selected_workspace = Some(id),
+            Action::UnselectWorkspace => {
+                self.selected_workspace = None;
+                self.tasks.clear();
+            }
+            Action::NewTasksData((tasks, workspace_id))
+                if self.selected_workspace == Some(workspace_id) =>
+            {
+                self.tasks = tasks;
+            }
+            Action::SortTasks(sorter) => {
+                if let Some(id) = self.selected_workspace {
+                    self.sorters.insert(id, sorter);
+                }
+            }
+            Action::PendingKeys(keys) => self.pending = keys,
+            Action::Info(message) => self.message = Some((StyleName::Info, message)),
+            Action::Error(message) => self.message = Some((StyleName::Error, message)),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let bar_style = self.config.styles[&StyleName::StatusBar];
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+
+        let mode = match self.mode {
+            Mode::Insert => "INSERT",
+            _ => "NAVIGATION",
+        };
+        let mut spans = vec![
+            Span::styled(format!(" {mode} "), bold.add_modifier(Modifier::REVERSED)),
+            Span::raw(format!(" {:?}", self.focused)),
+        ];
+        if let Some(name) = self.workspace_name() {
+            let done = self.tasks.iter().filter(|task| task.completed).count();
+            spans.push(Span::raw(" │ "));
+            spans.push(Span::styled(name.to_string(), bold));
+            spans.push(Span::raw(format!(" {done}/{} done", self.tasks.len())));
+            if let Some(sorter) = self.sorters.get(&self.selected_workspace.unwrap()) {
+                spans.push(Span::raw(format!(" │ sorted by {sorter}")));
+            }
+        }
+        if !self.pending.is_empty() {
+            spans.push(Span::raw(" │ "));
+            spans.push(Span::styled(key_sequence_to_string(&self.pending), bold));
+        }
+        let left = Line::from(spans);
+
+        // the message takes what the state leaves of the line.
+        let [left_area, message_area] =
+            Layout::horizontal([Constraint::Length(left.width() as u16), Constraint::Fill(1)])
+                .areas(area);
+        frame.render_widget(Block::default().style(bar_style), area);
+        frame.render_widget(Paragraph::new(left), left_area);
+        if let Some((style_name, message)) = &self.message {
+            let message = Paragraph::new(format!("{message} "))
+                .style(self.config.styles[style_name])
+                .alignment(Alignment::Right);
+            frame.render_widget(message, message_area);
+        }
+        Ok(())
+    }
+}
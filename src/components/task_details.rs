@@ -0,0 +1,150 @@
+use super::Component;
+use crate::action::Action;
+use crate::config::{Config, StyleName};
+use crate::structs::*;
+use chrono::Local;
+use color_eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+/// Pane showing everything about the selected task, the description is wrapped in full.
+#[derive(Debug, Default)]
+pub struct TaskDetails {
+    task: Option<Task>,
+    workspaces: Vec<Workspace>,
+    /// the tasks of the workspace of the selected task, to name its parent and count its subtasks.
+    tasks: Vec<Task>,
+    config: Config,
+}
+
+impl TaskDetails {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lines<'a>(&self, task: &'a Task) -> Vec<Line<'a>> {
+        let label_style = Style::default().add_modifier(Modifier::DIM);
+        let field = |label: &str, value: Span<'static>| {
+            Line::from(vec![
+                Span::styled(format!("{label:10}"), label_style),
+                value,
+            ])
+        };
+        let priority_style = [
+            StyleName::Priority1,
+            StyleName::Priority2,
+            StyleName::Priority3,
+            StyleName::Priority4,
+        ][task.priority.clamp(1, 4) as usize - 1];
+
+        let mut lines = vec![Line::styled(
+            task.name.clone(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        if !task.tags.is_empty() {
+            let tag_style = self.config.styles[&StyleName::Tag];
+            let mut spans = Vec::new();
+            for tag in &task.tags {
+                spans.push(Span::styled(format!(" {tag} "), tag_style));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::default());
+
+        if let Some(workspace) = self.workspaces.iter().find(|w| w.id == task.workspace_id) {
+            lines.push(field("Workspace", Span::raw(workspace.name.clone())));
+        }
+        if let Some(parent) = task
+            .parent_id
+            .and_then(|parent_id| self.tasks.iter().find(|t| t.id == parent_id))
+        {
+            lines.push(field("Parent", Span::raw(parent.name.clone())));
+        }
+        let subtasks: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|t| t.parent_id == Some(task.id))
+            .collect();
+        if !subtasks.is_empty() {
+            let done = subtasks.iter().filter(|t| t.completed).count();
+            lines.push(field(
+                "Subtasks",
+                Span::raw(format!("{done}/{} done", subtasks.len())),
+            ));
+        }
+        let status = match self.config.statuses.of(task) {
+            Some(status) => Span::styled(status.name.clone(), status.style),
+            None if task.completed => {
+                Span::styled("done", self.config.styles[&StyleName::Completed])
+            }
+            None => Span::raw("to do"),
+        };
+        lines.push(field("Status", status));
+        lines.push(field(
+            "Priority",
+            Span::styled(
+                priority_letter(task.priority).to_string(),
+                self.config.styles[&priority_style],
+            ),
+        ));
+        if let Some(due) = &task.due_date {
+            let due = Span::raw(format_date(due));
+            let due = if task.is_overdue(Local::now().naive_local()) {
+                due.style(self.config.styles[&StyleName::Overdue])
+            } else {
+                due
+            };
+            lines.push(field("Due", due));
+        }
+        if let Some(start) = &task.start_date {
+            lines.push(field("Starts", Span::raw(format_date(start))));
+        }
+        if let Some(recurrence) = &task.recurrence {
+            lines.push(field("Repeats", Span::raw(recurrence.to_string())));
+        }
+        lines.push(field("Created", Span::raw(format_date(&task.create_date))));
+        if let Some(completed_at) = &task.completed_at {
+            lines.push(field("Completed", Span::raw(format_date(completed_at))));
+        }
+
+        if !task.description.is_empty() {
+            lines.push(Line::default());
+            lines.extend(task.description.lines().map(Line::raw));
+        }
+        lines
+    }
+}
+
+impl Component for TaskDetails {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::SelectTask(task) => self.task = task,
+            Action::NewWorkspacesData(workspaces) => self.workspaces = workspaces,
+            Action::NewTasksData((tasks, _)) => self.tasks = tasks,
+            Action::UnselectWorkspace => {
+                self.task = None;
+                self.tasks.clear();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let block = Block::default()
+            .title("Details")
+            .border_type(BorderType::Thick)
+            .borders(Borders::ALL);
+        let details = match &self.task {
+            Some(task) => Paragraph::new(self.lines(task)).wrap(Wrap { trim: false }),
+            None => Paragraph::new("No task selected").alignment(Alignment::Center),
+        };
+        frame.render_widget(details.block(block), area);
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use super::{centered_rect, Component};
+use crate::action::Action;
+use crate::app::ComponentId;
+use crate::config::{Config, StyleName};
+use crate::structs::{format_date, Task, TaskEvent, TaskEventKind};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Popup listing what happened to the selected task, the last change first.
+#[derive(Debug)]
+pub struct TaskHistory {
+    is_focused: bool,
+    objective: ComponentId,
+    task: Option<Task>,
+    events: Vec<TaskEvent>,
+    state: ListState,
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+}
+
+impl TaskHistory {
+    pub fn new() -> Self {
+        Self {
+            is_focused: false,
+            objective: ComponentId::Tasks,
+            task: None,
+            events: Vec::new(),
+            state: ListState::default(),
+            command_tx: None,
+            config: Config::default(),
+        }
+    }
+
+    fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ExitTaskHistory(self.objective))?;
+            }
+            KeyCode::Down | KeyCode::Char('j') if !self.events.is_empty() => {
+                let selected = self.state.selected().map_or(0, |selected| selected + 1);
+                self.state.select(Some(selected % self.events.len()));
+            }
+            KeyCode::Up | KeyCode::Char('k') if !self.events.is_empty() => {
+                let selected = self.state.selected().unwrap_or_default();
+                self.state
+                    .select(Some((selected + self.events.len() - 1) % self.events.len()));
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.state.select(Some(0)),
+            KeyCode::End | KeyCode::Char('G') => {
+                self.state.select(Some(self.events.len().saturating_sub(1)));
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+}
+
+/// what the event changed, a task moved to the top level has no parent to name.
+fn change(event: &TaskEvent) -> String {
+    match (event.kind, &event.old_value, &event.new_value) {
+        (TaskEventKind::Moved, old, new) => format!(
+            "{} → {}",
+            old.as_deref().unwrap_or("top level"),
+            new.as_deref().unwrap_or("top level")
+        ),
+        (_, Some(old), Some(new)) => format!("{old} → {new}"),
+        _ => String::new(),
+    }
+}
+
+impl Component for TaskHistory {
+    fn focus(&mut self, focus: bool) -> Result<()> {
+        self.is_focused = focus;
+        self.events.clear();
+        if let (true, Some(task)) = (focus, &self.task) {
+            self.command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::RequestTaskHistory(task.id))?;
+        }
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::SetupTaskHistory(component_id) => {
+                self.objective = component_id;
+                self.state.select(Some(0));
+            }
+            Action::SelectTask(task) => self.task = task,
+            Action::NewTaskHistory(events) => self.events = events,
+            Action::SendKeyEvent(key) => {
+                self.handle_insert_mode(key)?;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.is_focused {
+            return Ok(());
+        }
+        let block_style = self.config.styles[&StyleName::Highlight];
+        let selection_style = self.config.styles[&StyleName::Selected];
+
+        let title = match &self.task {
+            Some(task) => format!("History of {}", task.name),
+            None => "History".to_string(),
+        };
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(title)
+            .title_bottom(Line::from(" esc close ").right_aligned())
+            .style(block_style);
+
+        let items: Vec<ListItem> = self
+            .events
+            .iter()
+            .map(|event| {
+                let kind_style = match event.kind {
+                    TaskEventKind::Completed => self.config.styles[&StyleName::Completed],
+                    _ => Style::default().add_modifier(Modifier::DIM),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{} ", format_date(&event.created_at))),
+                    Span::styled(format!("{:13} ", event.kind), kind_style),
+                    Span::raw(change(event)),
+                ]))
+                .style(Style::default())
+            })
+            .collect();
+
+        let area = centered_rect(70, 60, area);
+        frame.render_widget(Clear, area); //this clears out the background
+        if items.is_empty() {
+            let message = match self.task {
+                Some(_) => "Nothing happened to this task yet",
+                None => "No task selected",
+            };
+            let empty = Paragraph::new(message).style(Style::default()).block(block);
+            frame.render_widget(empty, area);
+            return Ok(());
+        }
+        let items = List::new(items)
+            .block(block)
+            .highlight_style(selection_style)
+            .highlight_symbol(">>")
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(items, area, &mut self.state);
+        Ok(())
+    }
+}
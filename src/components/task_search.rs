@@ -0,0 +1,191 @@
+use super::{
+    centered_rect,
+    search::highlight_words,
+    text_input::{InputResponse, TextInput},
+    Component,
+};
+use crate::action::Action;
+use crate::app::ComponentId;
+use crate::config::{Config, StyleName};
+use crate::structs::SearchResult;
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Popup searching the tasks of every workspace, the selected result is opened in its workspace.
+#[derive(Debug)]
+pub struct TaskSearch {
+    is_focused: bool,
+    objective: ComponentId,
+    input: TextInput,
+    results: Vec<SearchResult>,
+    state: ListState,
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+}
+
+impl TaskSearch {
+    pub fn new() -> Self {
+        Self {
+            is_focused: false,
+            objective: ComponentId::Workspaces,
+            input: TextInput::default(),
+            results: Vec::new(),
+            state: ListState::default(),
+            command_tx: None,
+            config: Config::default(),
+        }
+    }
+
+    fn select_next(&mut self) {
+        if self.results.is_empty() {
+            return;
+        }
+        let selected = self
+            .state
+            .selected()
+            .map_or(0, |i| (i + 1) % self.results.len());
+        self.state.select(Some(selected));
+    }
+
+    fn select_previous(&mut self) {
+        if self.results.is_empty() {
+            return;
+        }
+        let selected = match self.state.selected() {
+            Some(0) | None => self.results.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(selected));
+    }
+
+    fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<()> {
+        let command_tx = self.command_tx.as_ref().unwrap();
+        match self.input.handle_key(key) {
+            InputResponse::Edited => {
+                command_tx.send(Action::SearchTasks(self.input.value().to_string()))?;
+                return Ok(());
+            }
+            InputResponse::Handled => return Ok(()),
+            InputResponse::Ignored => {}
+        }
+        match key.code {
+            KeyCode::Down | KeyCode::Tab => self.select_next(),
+            KeyCode::Up | KeyCode::BackTab => self.select_previous(),
+            KeyCode::Enter => {
+                if let Some(selected) = self.state.selected() {
+                    let task = self.results[selected].task.clone();
+                    command_tx.send(Action::JumpToTask(task))?;
+                    command_tx.send(Action::ExitTaskSearch(ComponentId::Tasks))?;
+                }
+            }
+            KeyCode::Esc => {
+                command_tx.send(Action::ExitTaskSearch(self.objective))?;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+}
+
+impl Component for TaskSearch {
+    fn focus(&mut self, focus: bool) -> Result<()> {
+        self.is_focused = focus;
+        if !focus {
+            self.input.clear();
+            self.results.clear();
+            self.state.select(None);
+        }
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.input.set_vim_mode(config.config.vim_input);
+        self.config = config;
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::SetupTaskSearch(component_id) => {
+                self.objective = component_id;
+            }
+            Action::NewSearchResults(results) => {
+                self.results = results;
+                self.state.select((!self.results.is_empty()).then_some(0));
+            }
+            Action::SendKeyEvent(key) => {
+                self.handle_insert_mode(key)?;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.is_focused {
+            return Ok(());
+        }
+        let block_style = self.config.styles[&StyleName::Highlight];
+        let selection_style = self.config.styles[&StyleName::Selected];
+        let match_style = self.config.styles[&StyleName::SearchMatch];
+
+        let area = centered_rect(70, 60, area);
+        frame.render_widget(Clear, area); //this clears out the background
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title("Search all workspaces")
+            .style(block_style);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [input_area, results_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        let (typed, cursor) = self.input.visible(input_area.width.saturating_sub(1));
+        let input = Paragraph::new(Line::from([vec![Span::raw("/")], typed.spans].concat()))
+            .block(Block::default().borders(Borders::BOTTOM))
+            .style(Style::default());
+        frame.render_widget(input, input_area);
+
+        let items: Vec<ListItem> = self
+            .results
+            .iter()
+            .map(|result| {
+                let mut title = vec![Span::styled(
+                    format!("{}: ", result.workspace),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )];
+                title.extend(highlight_words(
+                    &result.task.name,
+                    self.input.value(),
+                    match_style,
+                ));
+                let mut lines = vec![Line::from(title)];
+                if !result.snippet.is_empty() {
+                    lines.push(Line::from(highlight_words(
+                        &result.snippet,
+                        self.input.value(),
+                        match_style,
+                    )));
+                }
+                ListItem::new(lines).style(Style::default())
+            })
+            .collect();
+
+        let items = List::new(items)
+            .highlight_style(selection_style)
+            .highlight_symbol(">>")
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(items, results_area, &mut self.state);
+
+        frame.set_cursor(input_area.x + 1 + cursor, input_area.y);
+        Ok(())
+    }
+}
@@ -1,15 +1,20 @@
-use super::Component;
+use super::{
+    search::{highlight_matches, is_match, next_match},
+    text_input::{InputResponse, TextInput},
+    Component,
+};
 use crate::{
     action::Action,
     config::{Config, StyleName},
     structs::*,
 };
+use chrono::Local;
 use color_eyre::{eyre::Ok, Result};
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{prelude::*, widgets::*};
 use std::{
     cmp::{max, min},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
 };
 use tokio::sync::mpsc::UnboundedSender;
 
@@ -19,13 +24,24 @@ pub struct TasksComponent {
     config: Config,
     list: TasksTable,
     selected_workspace: Option<i32>,
-    input: String,
-    character_index: usize,
+    input: TextInput,
     mode: Mode,
     is_focused: bool,
     highlighted_item: (Option<usize>, String),
-    to_be_selected: Option<usize>,
+    to_be_selected: Option<(Option<i32>, usize)>, // the parent and order of the task to select once reloaded.
+    jump_to: Option<i32>, // the id of a task to select once reloaded, even if filtered out or folded.
     last_selected: HashMap<i32, usize>,
+    search: String,
+    search_origin: Option<usize>,
+    tag_filter: Vec<String>,
+    workspace_tasks: Vec<Task>, // every task of the workspace, the table only shows the filtered ones.
+    folded: HashSet<i32>,
+    subtasks: HashMap<i32, (usize, usize)>, // the completed and total number of subtasks of each task.
+    insert_position: (Option<i32>, usize, usize), // the parent, order and depth of the task being added.
+    shown_task: Option<Task>, // the selected task as last sent to the details pane.
+    hidden: bool,             // whether the board is shown in place of the table.
+    area: Rect,               // where the table was last drawn, to find the clicked rows.
+    dragged: Option<usize>,   // the row held down by the mouse.
 }
 
 #[derive(Default, PartialEq, Eq, Debug)]
@@ -35,23 +51,83 @@ enum Mode {
     Insert(usize),
     Edit(usize),
     EditDescription(usize),
+    EditDueDate(usize),
+    EditRecurrence(usize),
+    Search,
+    FilterTags,
 }
 
 #[derive(Default, Debug)]
 struct TasksTable {
     items: Vec<Task>,
+    depths: Vec<usize>,
     state: TableState,
 }
 
+/// where a task sits in the tree of its workspace.
+#[derive(Default, Debug, Clone, Copy)]
+struct TreeInfo {
+    depth: usize,
+    /// `None` for the tasks without subtasks.
+    folded: Option<bool>,
+    /// the completed and total number of subtasks.
+    progress: Option<(usize, usize)>,
+}
+
+impl TreeInfo {
+    /// the indentation and fold marker written before the name.
+    fn prefix(&self) -> String {
+        let marker = match self.folded {
+            Some(true) => "▸ ",
+            Some(false) => "▾ ",
+            None => "  ",
+        };
+        "  ".repeat(self.depth) + marker
+    }
+
+    fn width(&self) -> usize {
+        self.depth * 2 + 2
+    }
+}
+
+/// the cell of a row being edited, with the input shown in place of its content.
+#[derive(Debug, Clone)]
+enum EditedCell {
+    Name(Line<'static>),
+    Description(Line<'static>),
+    /// the due date or the recurrence, both are shown in the due column.
+    DueDate(Line<'static>),
+}
+
 impl Task {
+    /// the due date followed by the recurrence, `2024-05-06 ↻ weekly mon`.
+    fn due_text(&self) -> String {
+        let due = self.due_date.as_ref().map(format_date);
+        let recurrence = self
+            .recurrence
+            .as_ref()
+            .map(|recurrence| format!("↻ {recurrence}"));
+        due.into_iter()
+            .chain(recurrence)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     fn to_row(
         &self,
         config: &Config,
+        tree: TreeInfo,
         highlighting: &(Option<usize>, String),
-        override_name: Option<String>,
-        override_desc: Option<String>,
-    ) -> Row {
-        let prioritys = vec![
+        edited: Option<EditedCell>,
+        search: &str,
+    ) -> Row<'_> {
+        let (override_name, override_desc, override_due) = match edited {
+            Some(EditedCell::Name(name)) => (Some(name), None, None),
+            Some(EditedCell::Description(desc)) => (None, Some(desc), None),
+            Some(EditedCell::DueDate(due)) => (None, None, Some(due)),
+            None => (None, None, None),
+        };
+        let prioritys = [
             Cell::from(
                 Text::raw("A")
                     .style(config.styles[&StyleName::Priority1])
@@ -75,13 +151,54 @@ impl Task {
         ];
         let error_style = config.styles[&StyleName::Error];
         let completed_style = config.styles[&StyleName::Completed];
-        let check_cell = if self.completed {
-            Cell::from(" ✓").style(completed_style)
-        } else {
-            Cell::from(" ☐")
+        let overdue_style = config.styles[&StyleName::Overdue];
+        let match_style = config.styles[&StyleName::SearchMatch];
+        let tag_style = config.styles[&StyleName::Tag];
+        let check_cell = match tree.progress {
+            Some((done, total)) if self.completed || done == total => {
+                Cell::from(format!(" {done}/{total}")).style(completed_style)
+            }
+            Some((done, total)) => Cell::from(format!(" {done}/{total}")),
+            None => match config.statuses.of(self) {
+                Some(status) => Cell::from(format!(" {}", status.glyph)).style(status.style),
+                None if self.completed => Cell::from(" ✓").style(completed_style),
+                None => Cell::from(" ☐"),
+            },
+        };
+        let name = match override_name {
+            Some(name) => Line::from([vec![Span::raw(tree.prefix())], name.spans].concat()),
+            None => {
+                let mut spans = vec![Span::raw(tree.prefix())];
+                spans.extend(highlight_matches(&self.name, search, match_style));
+                for tag in &self.tags {
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::styled(format!(" {tag} "), tag_style));
+                }
+                Line::from(spans)
+            }
+        };
+        let description = match override_desc {
+            Some(description) => description,
+            // the other lines are shown by the description pane.
+            None => {
+                let (first_line, rest) = self
+                    .description
+                    .split_once('\n')
+                    .unwrap_or((&self.description, ""));
+                let mut spans = highlight_matches(first_line, search, match_style);
+                if !rest.trim().is_empty() {
+                    spans.push(Span::raw(" …"));
+                }
+                Line::from(spans)
+            }
+        };
+        let due_cell = match override_due {
+            Some(due) => Cell::from(due),
+            None if self.is_overdue(Local::now().naive_local()) => {
+                Cell::from(self.due_text()).style(overdue_style)
+            }
+            None => Cell::from(self.due_text()),
         };
-        let name = override_name.unwrap_or(self.name.clone());
-        let description = override_desc.unwrap_or(self.description.clone());
         let mut name_cell = if self.completed {
             Cell::from(name).style(completed_style)
         } else {
@@ -95,6 +212,7 @@ impl Task {
             check_cell,
             name_cell,
             prioritys[self.priority as usize - 1].clone(),
+            due_cell,
             description.into(),
         ])
     }
@@ -116,13 +234,30 @@ impl TasksComponent {
         }
     }
 
+    /// lets the other components know when the selected task or its data changed.
+    fn notify_selection(&mut self) -> Result<()> {
+        if self.hidden {
+            return Ok(());
+        }
+        let selected = self
+            .list
+            .state
+            .selected()
+            .and_then(|selected| self.list.items.get(selected))
+            .cloned();
+        if selected != self.shown_task {
+            self.shown_task.clone_from(&selected);
+            self.command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::SelectTask(selected))?;
+        }
+        Ok(())
+    }
+
     fn mark_task(&mut self) {
         if let Some(selected) = self.list.state.selected() {
-            let t = UpdateTask {
-                id: self.list.items[selected].id,
-                completed: Some(!self.list.items[selected].completed),
-                ..Default::default()
-            };
+            let t = self.list.items[selected].toggle_completion(Local::now().naive_local());
             self.command_tx
                 .as_ref()
                 .unwrap()
@@ -195,47 +330,275 @@ impl TasksComponent {
         self.on_select();
     }
 
+    /// shows the tree of the workspace without the subtasks of the folded tasks, and only the
+    /// tasks having every tag of the filter.
+    fn refresh_items(&mut self) {
+        self.subtasks.clear();
+        for task in &self.workspace_tasks {
+            if let Some(parent_id) = task.parent_id {
+                let (done, total) = self.subtasks.entry(parent_id).or_default();
+                *total += 1;
+                if task.completed {
+                    *done += 1;
+                }
+            }
+        }
+
+        let mut items = Vec::new();
+        let mut depths = Vec::new();
+        let mut folded_depth = None;
+        for (depth, task) in task_tree(&self.workspace_tasks) {
+            match folded_depth {
+                Some(folded) if depth > folded => continue,
+                _ => folded_depth = None,
+            }
+            if self.folded.contains(&task.id) {
+                folded_depth = Some(depth);
+            }
+            if self.tag_filter.iter().all(|tag| task.tags.contains(tag)) {
+                items.push(task.clone());
+                depths.push(depth);
+            }
+        }
+        self.list.items = items;
+        self.list.depths = depths;
+        self.secure_selction();
+    }
+
+    fn tree_info(&self, index: usize) -> TreeInfo {
+        let task = &self.list.items[index];
+        TreeInfo {
+            depth: self.list.depths[index],
+            folded: self
+                .subtasks
+                .contains_key(&task.id)
+                .then(|| self.folded.contains(&task.id)),
+            progress: self.subtasks.get(&task.id).copied(),
+        }
+    }
+
+    /// unfolds the ancestors of the tasks under `parent_id` so they are shown.
+    fn reveal(&mut self, mut parent_id: Option<i32>) {
+        while let Some(id) = parent_id {
+            self.folded.remove(&id);
+            parent_id = self
+                .workspace_tasks
+                .iter()
+                .find(|task| task.id == id)
+                .and_then(|task| task.parent_id);
+        }
+    }
+
+    fn count_subtasks(&self, parent_id: Option<i32>) -> usize {
+        self.workspace_tasks
+            .iter()
+            .filter(|task| task.parent_id == parent_id)
+            .count()
+    }
+
+    /// the index right after the task at `index` and its shown subtasks.
+    fn subtree_end(&self, index: usize) -> usize {
+        let depth = self.list.depths[index];
+        let mut end = index + 1;
+        while end < self.list.depths.len() && self.list.depths[end] > depth {
+            end += 1;
+        }
+        end
+    }
+
+    /// makes the selected task a subtask of the task above it among its siblings.
+    fn indent(&mut self) -> Result<()> {
+        let Some(selected) = self.list.state.selected() else {
+            return Ok(());
+        };
+        let task = &self.list.items[selected];
+        let Some(previous) = task.order.checked_sub(1).and_then(|order| {
+            self.workspace_tasks
+                .iter()
+                .find(|t| t.parent_id == task.parent_id && t.order == order)
+        }) else {
+            return Ok(());
+        };
+        let t = UpdateTask {
+            id: task.id,
+            parent_id: Some(Some(previous.id)),
+            ..Default::default()
+        };
+        self.to_be_selected = Some((Some(previous.id), self.count_subtasks(Some(previous.id))));
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::UpdateTask(t))?;
+        Ok(())
+    }
+
+    /// moves the selected task out of its parent, right after it.
+    fn outdent(&mut self) -> Result<()> {
+        let Some(selected) = self.list.state.selected() else {
+            return Ok(());
+        };
+        let task = &self.list.items[selected];
+        let Some(parent) = self
+            .workspace_tasks
+            .iter()
+            .find(|t| Some(t.id) == task.parent_id)
+        else {
+            return Ok(());
+        };
+        let t = UpdateTask {
+            id: task.id,
+            parent_id: Some(parent.parent_id),
+            order: Some(parent.order + 1),
+            ..Default::default()
+        };
+        self.to_be_selected = Some((parent.parent_id, parent.order + 1));
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::UpdateTask(t))?;
+        Ok(())
+    }
+
+    fn toggle_fold(&mut self) {
+        if let Some(selected) = self.list.state.selected() {
+            let id = self.list.items[selected].id;
+            if !self.subtasks.contains_key(&id) {
+                return;
+            }
+            if !self.folded.remove(&id) {
+                self.folded.insert(id);
+            }
+            self.refresh_items();
+        }
+    }
+
+    fn matches(&self) -> Vec<bool> {
+        self.list
+            .items
+            .iter()
+            .map(|t| is_match(&t.name, &self.search) || is_match(&t.description, &self.search))
+            .collect()
+    }
+
+    /// selects the next task matching the search, or the previous one when `backward` is set.
+    fn select_match(&mut self, from: Option<usize>, backward: bool) {
+        if let Some(index) = next_match(&self.matches(), from, backward) {
+            self.list.state.select(Some(index));
+            self.on_select();
+        }
+    }
+
     fn submit(&mut self) -> Result<()> {
         let command_tx = self.command_tx.as_ref().unwrap();
         match self.mode {
-            Mode::Insert(target) => {
+            Mode::Search => {
+                command_tx.send(Action::LeaveInsertMode)?;
+                self.input.clear();
+                self.mode = Mode::Normal;
+            }
+            Mode::FilterTags => {
+                // the `#` is optional when typing the tags to filter by.
+                self.tag_filter = normalize_tags(
+                    self.input
+                        .value()
+                        .split_whitespace()
+                        .map(|word| word.trim_start_matches('#').to_string())
+                        .filter(|tag| !tag.is_empty()),
+                );
+                self.refresh_items();
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::LeaveInsertMode)?;
+                self.input.clear();
+                self.mode = Mode::Normal;
+            }
+            Mode::Insert(_) => {
+                // the new task gets the tags of the filter so it stays visible.
+                let (name, tags) = split_tags(self.input.value());
+                let (parent_id, order, _) = self.insert_position;
                 let t = AddTask {
-                    name: self.input.trim().to_string(),
-                    order: Some(target),
+                    name,
+                    order: Some(order),
                     workspace_id: self.selected_workspace.unwrap(),
+                    tags: normalize_tags(tags.into_iter().chain(self.tag_filter.clone())),
+                    parent_id,
                     ..Default::default()
                 };
                 command_tx.send(Action::AddTask(t))?;
                 command_tx.send(Action::LeaveInsertMode)?;
-                self.to_be_selected = Some(target);
+                self.to_be_selected = Some((parent_id, order));
                 self.input.clear();
-                self.character_index = 0;
                 self.mode = Mode::Normal;
             }
             Mode::Edit(target) => {
+                let (name, tags) = split_tags(self.input.value());
                 let t = UpdateTask {
                     id: self.list.items[target].id,
-                    name: Some(self.input.trim().to_string()),
+                    name: Some(name),
+                    tags: Some(tags),
                     ..Default::default()
                 };
                 command_tx.send(Action::UpdateTask(t))?;
                 command_tx.send(Action::LeaveInsertMode)?;
-                self.to_be_selected = Some(target);
+                let task = &self.list.items[target];
+                self.to_be_selected = Some((task.parent_id, task.order));
                 self.input.clear();
-                self.character_index = 0;
                 self.mode = Mode::Normal;
             }
             Mode::EditDescription(target) => {
                 let t = UpdateTask {
                     id: self.list.items[target].id,
-                    description: Some(self.input.trim().to_string()),
+                    description: Some(self.input.value().trim().to_string()),
+                    ..Default::default()
+                };
+                command_tx.send(Action::UpdateTask(t))?;
+                command_tx.send(Action::LeaveInsertMode)?;
+                let task = &self.list.items[target];
+                self.to_be_selected = Some((task.parent_id, task.order));
+                self.input.clear();
+                self.mode = Mode::Normal;
+            }
+            Mode::EditDueDate(target) => {
+                // an empty input clears the due date, an invalid one keeps the editor open.
+                let due_date = if self.input.value().trim().is_empty() {
+                    None
+                } else if let Some(date) = parse_date_input(self.input.value()) {
+                    Some(date)
+                } else {
+                    return Ok(());
+                };
+                let t = UpdateTask {
+                    id: self.list.items[target].id,
+                    due_date: Some(due_date),
+                    ..Default::default()
+                };
+                command_tx.send(Action::UpdateTask(t))?;
+                command_tx.send(Action::LeaveInsertMode)?;
+                let task = &self.list.items[target];
+                self.to_be_selected = Some((task.parent_id, task.order));
+                self.input.clear();
+                self.mode = Mode::Normal;
+            }
+            Mode::EditRecurrence(target) => {
+                // an empty input stops the recurrence, an invalid one keeps the editor open.
+                let recurrence = if self.input.value().trim().is_empty() {
+                    None
+                } else if let std::result::Result::Ok(recurrence) = self.input.value().parse() {
+                    Some(recurrence)
+                } else {
+                    return Ok(());
+                };
+                let t = UpdateTask {
+                    id: self.list.items[target].id,
+                    recurrence: Some(recurrence),
                     ..Default::default()
                 };
                 command_tx.send(Action::UpdateTask(t))?;
                 command_tx.send(Action::LeaveInsertMode)?;
-                self.to_be_selected = Some(target);
+                let task = &self.list.items[target];
+                self.to_be_selected = Some((task.parent_id, task.order));
                 self.input.clear();
-                self.character_index = 0;
                 self.mode = Mode::Normal;
             }
             _ => unreachable!(),
@@ -243,6 +606,20 @@ impl TasksComponent {
         Ok(())
     }
 
+    fn cycle_status(&self, backward: bool) -> Result<()> {
+        if let Some(selected) = self.list.state.selected() {
+            let task = &self.list.items[selected];
+            if let Some(status) = self.config.statuses.cycle(task, backward) {
+                let t = task.set_status(&status.name, status.done, Local::now().naive_local());
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::UpdateTask(t))?;
+            }
+        }
+        Ok(())
+    }
+
     fn decrease_priority(&self) -> Result<()> {
         if let Some(selected) = self.list.state.selected() {
             let t = UpdateTask {
@@ -273,39 +650,91 @@ impl TasksComponent {
         Ok(())
     }
 
+    /// the row of the table at the line `row` of the screen.
+    fn row_at(&self, row: u16) -> Option<usize> {
+        // the rows start under the border and the header.
+        if row + 1 >= self.area.bottom() {
+            return None;
+        }
+        let index = row.checked_sub(self.area.y + 2)? as usize + self.list.state.offset();
+        (index < self.list.items.len()).then_some(index)
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        let command_tx = self.command_tx.as_ref().unwrap().clone();
+        let position = Position::new(mouse.column, mouse.row);
+        if !self.area.contains(position) {
+            self.dragged = None;
+            return Ok(());
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if !self.is_focused {
+                    command_tx.send(Action::FocusOnTasks)?;
+                }
+                if let Some(index) = self.row_at(mouse.row) {
+                    self.list.state.select(Some(index));
+                    self.on_select();
+                    // the checkbox comes after the border and the highlight symbol.
+                    if (self.area.x + 3..self.area.x + 8).contains(&mouse.column) {
+                        self.mark_task();
+                    } else {
+                        self.dragged = Some(index);
+                    }
+                }
+            }
+            // a task dropped on one of its siblings takes its place.
+            MouseEventKind::Up(MouseButton::Left) => {
+                let Some(from) = self.dragged.take() else {
+                    return Ok(());
+                };
+                let Some(to) = self.row_at(mouse.row) else {
+                    return Ok(());
+                };
+                let (task, target) = (&self.list.items[from], &self.list.items[to]);
+                if from != to && task.parent_id == target.parent_id {
+                    let t = UpdateTask {
+                        id: task.id,
+                        order: Some(target.order),
+                        ..Default::default()
+                    };
+                    self.to_be_selected = Some((task.parent_id, target.order));
+                    command_tx.send(Action::UpdateTask(t))?;
+                }
+            }
+            MouseEventKind::ScrollDown => self.select_next(),
+            MouseEventKind::ScrollUp => self.select_previous(),
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<()> {
         let command_tx = self.command_tx.as_ref().unwrap();
-        match key.code {
-            KeyCode::Char(c) => {
-                self.input.insert(self.character_index, c);
-                self.character_index += 1;
-            }
-            KeyCode::Backspace => {
-                if self.character_index > 0 {
-                    self.character_index -= 1;
-                    self.input.remove(self.character_index);
-                }
+        if self.input.handle_key(key) != InputResponse::Ignored {
+            // searching as you type, starting from the task selected when the search began.
+            if self.mode == Mode::Search && self.search != self.input.value() {
+                self.search = self.input.value().to_string();
+                self.select_match(self.search_origin.and_then(|i| i.checked_sub(1)), false);
             }
+            return Ok(());
+        }
+        match key.code {
             KeyCode::Enter => {
                 self.submit()?;
+                return Ok(());
             }
             KeyCode::Esc => {
                 command_tx.send(Action::LeaveInsertMode)?;
+                if self.mode == Mode::Search {
+                    self.search.clear();
+                    self.list.state.select(self.search_origin);
+                    self.on_select();
+                }
                 self.input.clear();
-                self.character_index = 0;
                 self.mode = Mode::Normal;
                 self.secure_selction();
             }
-            KeyCode::Left => {
-                if self.character_index > 0 {
-                    self.character_index -= 1;
-                }
-            }
-            KeyCode::Right => {
-                if self.character_index < self.input.len() {
-                    self.character_index += 1;
-                }
-            }
             _ => {}
         };
         Ok(())
@@ -314,6 +743,7 @@ impl TasksComponent {
 
 impl Component for TasksComponent {
     fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.input.set_vim_mode(config.config.vim_input);
         self.config = config;
         Ok(())
     }
@@ -332,13 +762,13 @@ impl Component for TasksComponent {
         let command_tx = self.command_tx.as_ref().unwrap().clone();
         match action {
             Action::NewTasksData((tasks, workspace_id)) => {
-                self.list.items = tasks;
-                TaskSorter::default().sort(&mut self.list.items);
+                self.workspace_tasks = tasks;
+                TaskSorter::default().sort(&mut self.workspace_tasks);
 
                 // making sure the database is not fucking up the order(again).
-                let mut iter = self.list.items.iter();
-                if let Some(mut last_order) = iter.next().map(|t| t.order) {
-                    for task in iter {
+                let mut last_orders: HashMap<Option<i32>, usize> = HashMap::new();
+                for task in &self.workspace_tasks {
+                    if let Some(last_order) = last_orders.insert(task.parent_id, task.order) {
                         assert!(
                             task.order == last_order + 1,
                             "the order is not as expected at workspace {} because we found orders:{},{}",
@@ -346,19 +776,41 @@ impl Component for TasksComponent {
                             task.order,
                             last_order
                         );
-                        last_order = task.order;
                     }
                 }
 
                 self.selected_workspace = Some(workspace_id);
+                if let Some((parent_id, _)) = self.to_be_selected {
+                    self.reveal(parent_id);
+                }
+                let jumped = self.jump_to.take().and_then(|id| {
+                    self.workspace_tasks
+                        .iter()
+                        .find(|task| task.id == id)
+                        .cloned()
+                });
+                if let Some(task) = &jumped {
+                    self.reveal(task.parent_id);
+                    if !self.tag_filter.iter().all(|tag| task.tags.contains(tag)) {
+                        self.tag_filter.clear();
+                    }
+                }
+                self.refresh_items();
 
                 // selection handling
+                let to_be_selected = match jumped {
+                    Some(task) => self.list.items.iter().position(|t| t.id == task.id),
+                    None => self.to_be_selected.take().and_then(|(parent_id, order)| {
+                        self.list
+                            .items
+                            .iter()
+                            .position(|t| t.parent_id == parent_id && t.order == order)
+                    }),
+                };
                 if self.list.items.is_empty() {
                     self.list.state.select(None);
-                } else if let Some(index) = self.to_be_selected {
-                    let index = min(index, self.list.items.len() - 1);
+                } else if let Some(index) = to_be_selected {
                     self.list.state.select(Some(index));
-                    self.to_be_selected = None;
                 } else if let Some(last_selection) = self.last_selected.get(&workspace_id) {
                     let last_selection = min(*last_selection, self.list.items.len() - 1);
                     self.list.state.select(Some(last_selection));
@@ -372,19 +824,31 @@ impl Component for TasksComponent {
                 self.on_select();
             }
             Action::SortTasks(sorter) => {
-                let mut items = self.list.items.clone();
-                sorter.sort(&mut items);
-                for (new_order, item) in items.iter().enumerate() {
-                    let t = UpdateTask {
+                // the subtasks are sorted among their siblings.
+                let mut items = self.workspace_tasks.clone();
+                let statuses = &self.config.statuses;
+                sorter.sort_with_status_rank(&mut items, |task| statuses.rank(task));
+                let mut new_orders: HashMap<Option<i32>, usize> = HashMap::new();
+                let mut updates = Vec::with_capacity(items.len());
+                for item in items.iter() {
+                    let new_order = new_orders.entry(item.parent_id).or_default();
+                    updates.push(UpdateTask {
                         id: item.id,
-                        order: Some(new_order),
+                        order: Some(*new_order),
                         ..Default::default()
-                    };
-                    command_tx.send(Action::UpdateTask(t))?;
+                    });
+                    *new_order += 1;
                 }
+                // the whole sort is undone at once.
+                command_tx.send(Action::UpdateTasks(updates))?;
                 self.secure_selction();
             }
+            Action::JumpToTask(task) => {
+                self.to_be_selected = None;
+                self.jump_to = Some(task.id);
+            }
             Action::UnselectWorkspace => {
+                self.workspace_tasks.clear();
                 self.list.items.clear();
                 self.selected_workspace = None;
                 self.list.state.select(None);
@@ -396,6 +860,13 @@ impl Component for TasksComponent {
             Action::GoDown => {
                 self.select_next();
             }
+            Action::GoLeft => {
+                command_tx.send(Action::FocusOnWorkspaces)?;
+            }
+            Action::ShowBoard(shown) => {
+                self.hidden = shown;
+                self.shown_task = None;
+            }
             Action::GoToTop => {
                 self.select_top();
             }
@@ -416,13 +887,21 @@ impl Component for TasksComponent {
                 );
                 self.handle_insert_mode(key)?;
             }
+            Action::SendMouseEvent(mouse) if self.mode == Mode::Normal => {
+                self.handle_mouse_event(mouse)?;
+            }
             Action::AddItemAfter => {
                 if self.selected_workspace.is_none() {
                     return Ok(());
                 }
+                // the new task goes after the selected one and its subtasks.
                 if let Some(selected) = self.list.state.selected() {
-                    self.mode = Mode::Insert(selected + 1);
+                    let task = &self.list.items[selected];
+                    self.insert_position =
+                        (task.parent_id, task.order + 1, self.list.depths[selected]);
+                    self.mode = Mode::Insert(self.subtree_end(selected));
                 } else {
+                    self.insert_position = (None, self.count_subtasks(None), 0);
                     self.mode = Mode::Insert(self.list.items.len());
                 }
                 command_tx.send(Action::EnterInsertMode)?;
@@ -432,8 +911,11 @@ impl Component for TasksComponent {
                     return Ok(());
                 }
                 if let Some(selected) = self.list.state.selected() {
+                    let task = &self.list.items[selected];
+                    self.insert_position = (task.parent_id, task.order, self.list.depths[selected]);
                     self.mode = Mode::Insert(selected);
                 } else {
+                    self.insert_position = (None, 0, 0);
                     self.mode = Mode::Insert(0);
                 }
                 command_tx.send(Action::EnterInsertMode)?;
@@ -441,25 +923,102 @@ impl Component for TasksComponent {
             Action::ToggleCompletion => {
                 self.mark_task();
             }
+            Action::NextStatus => {
+                self.cycle_status(false)?;
+            }
+            Action::PreviousStatus => {
+                self.cycle_status(true)?;
+            }
             Action::DeleteItem => {
                 if let Some(selected) = self.list.state.selected() {
                     command_tx.send(Action::RemoveTask(self.list.items[selected].id))?;
                 }
             }
+            // a description of several lines does not fit the cell, it goes to the editor.
+            Action::EditDescription
+                if self.list.state.selected().is_some_and(|selected| {
+                    self.list.items[selected].description.contains('\n')
+                }) =>
+            {
+                let t = &self.list.items[self.list.state.selected().unwrap()];
+                command_tx.send(Action::OpenEditor(t.id, t.description.clone()))?;
+            }
+            Action::EditDescriptionInEditor => {
+                if let Some(selected) = self.list.state.selected() {
+                    let t = &self.list.items[selected];
+                    command_tx.send(Action::OpenEditor(t.id, t.description.clone()))?;
+                }
+            }
             Action::EditDescription => {
                 if let Some(selected) = self.list.state.selected() {
                     let t = &self.list.items[selected];
                     self.mode = Mode::EditDescription(selected);
-                    self.input.clone_from(&t.description);
-                    self.character_index = self.input.len();
+                    self.input.set_value(&t.description);
+                    command_tx.send(Action::EnterInsertMode)?;
+                }
+            }
+            Action::Search if self.selected_workspace.is_some() => {
+                self.mode = Mode::Search;
+                self.input.set_value(&self.search);
+                self.search_origin = self.list.state.selected();
+                command_tx.send(Action::EnterInsertMode)?;
+            }
+            Action::NextMatch => {
+                self.select_match(self.list.state.selected(), false);
+            }
+            Action::PreviousMatch => {
+                self.select_match(self.list.state.selected(), true);
+            }
+            Action::Cancel => {
+                // the search is cleared first, then the tag filter.
+                if !self.search.is_empty() {
+                    self.search.clear();
+                } else if !self.tag_filter.is_empty() {
+                    self.tag_filter.clear();
+                    self.refresh_items();
+                }
+            }
+            Action::FilterTags if self.selected_workspace.is_some() => {
+                self.mode = Mode::FilterTags;
+                self.input.set_value(&format_tags(&self.tag_filter));
+                command_tx.send(Action::EnterInsertMode)?;
+            }
+            Action::EditDueDate => {
+                if let Some(selected) = self.list.state.selected() {
+                    self.mode = Mode::EditDueDate(selected);
+                    self.input.set_value(
+                        &self.list.items[selected]
+                            .due_date
+                            .as_ref()
+                            .map(format_date)
+                            .unwrap_or_default(),
+                    );
+                    command_tx.send(Action::EnterInsertMode)?;
+                }
+            }
+            Action::EditRecurrence => {
+                if let Some(selected) = self.list.state.selected() {
+                    self.mode = Mode::EditRecurrence(selected);
+                    self.input.set_value(
+                        &self.list.items[selected]
+                            .recurrence
+                            .as_ref()
+                            .map(Recurrence::to_string)
+                            .unwrap_or_default(),
+                    );
                     command_tx.send(Action::EnterInsertMode)?;
                 }
             }
             Action::EditItem => {
                 if let Some(selected) = self.list.state.selected() {
+                    let t = &self.list.items[selected];
                     self.mode = Mode::Edit(selected);
-                    self.input.clone_from(&self.list.items[selected].name);
-                    self.character_index = self.input.len();
+                    let name = if t.tags.is_empty() {
+                        t.name.clone()
+                    } else {
+                        format!("{} {}", t.name, format_tags(&t.tags))
+                    };
+                    self.input.set_value(&name);
                     command_tx.send(Action::EnterInsertMode)?;
                 }
             }
@@ -476,60 +1035,48 @@ impl Component for TasksComponent {
                 }
                 None => {}
             },
-            Action::MoveItemTop => {
+            // the tasks move among their siblings.
+            Action::MoveItemTop
+            | Action::MoveItemUp
+            | Action::MoveItemDown
+            | Action::MoveItemBottom => {
                 if let Some(selected) = self.list.state.selected() {
-                    let t = UpdateTask {
-                        id: self.list.items[selected].id,
-                        order: Some(0),
-                        ..Default::default()
+                    let task = &self.list.items[selected];
+                    let last = self.count_subtasks(task.parent_id) - 1;
+                    let order = match action {
+                        Action::MoveItemTop => 0,
+                        Action::MoveItemUp => task.order.saturating_sub(1),
+                        Action::MoveItemDown => min(task.order + 1, last),
+                        _ => last,
                     };
-                    command_tx.send(Action::UpdateTask(t))?;
-                    self.select_top();
-                }
-            }
-            Action::MoveItemUp => {
-                if let Some(selected) = self.list.state.selected() {
-                    if selected > 0 {
+                    if order != task.order {
                         let t = UpdateTask {
-                            id: self.list.items[selected].id,
-                            order: Some(selected - 1),
+                            id: task.id,
+                            order: Some(order),
                             ..Default::default()
                         };
+                        self.to_be_selected = Some((task.parent_id, order));
                         command_tx.send(Action::UpdateTask(t))?;
-                        self.select_previous();
                     }
                 }
             }
-            Action::MoveItemDown => {
-                if let Some(selected) = self.list.state.selected() {
-                    if selected < self.list.items.len() - 1 {
-                        let t = UpdateTask {
-                            id: self.list.items[selected].id,
-                            order: Some(selected + 1),
-                            ..Default::default()
-                        };
-                        command_tx.send(Action::UpdateTask(t))?;
-                        self.select_next();
-                    }
-                }
+            Action::IndentItem => {
+                self.indent()?;
             }
-            Action::MoveItemBottom => {
-                if let Some(selected) = self.list.state.selected() {
-                    let t = UpdateTask {
-                        id: self.list.items[selected].id,
-                        order: Some(self.list.items.len() - 1),
-                        ..Default::default()
-                    };
-                    command_tx.send(Action::UpdateTask(t))?;
-                    self.select_bottom();
-                }
+            Action::OutdentItem => {
+                self.outdent()?;
+            }
+            Action::ToggleFold => {
+                self.toggle_fold();
             }
             _ => {}
         }
+        self.notify_selection()?;
         Ok(())
     }
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        self.area = area;
         let selected_style = self.config.styles[&StyleName::Selected];
         let block_style = if self.is_focused {
             self.config.styles[&StyleName::Highlight]
@@ -537,8 +1084,43 @@ impl Component for TasksComponent {
             Style::default()
         };
 
+        // the query and the tags are typed in the title.
+        let (typed, typed_cursor) = match self.mode {
+            Mode::Search | Mode::FilterTags => {
+                let (line, cursor) = self.input.visible(area.width.saturating_sub(20));
+                (line.to_string(), cursor)
+            }
+            _ => (String::new(), 0),
+        };
+        // the search indicator shows the query and the position of the selection among matches.
+        let title = if self.mode == Mode::Search || !self.search.is_empty() {
+            let matches = self.matches();
+            let total = matches.iter().filter(|m| **m).count();
+            let current = match self.list.state.selected() {
+                Some(selected) if matches.get(selected) == Some(&true) => {
+                    (matches[..=selected].iter().filter(|m| **m).count()).to_string()
+                }
+                _ => "-".to_string(),
+            };
+            let search = if self.mode == Mode::Search {
+                &typed
+            } else {
+                &self.search
+            };
+            format!("Tasks /{} [{}/{}]", search, current, total)
+        } else if self.mode == Mode::FilterTags {
+            format!("Tasks tags: {}", typed)
+        } else {
+            "Tasks".to_string()
+        };
+        let title = if self.tag_filter.is_empty() || self.mode == Mode::FilterTags {
+            title
+        } else {
+            format!("{} {}", title, format_tags(&self.tag_filter))
+        };
+
         let block = Block::default()
-            .title("Tasks")
+            .title(title)
             .border_style(block_style)
             .border_type(BorderType::Thick)
             .borders(Borders::ALL);
@@ -551,25 +1133,64 @@ impl Component for TasksComponent {
             return Ok(());
         }
 
-        let mut columns_sizes: (u16, u16, u16) = (4, 8, 11);
+        let mut columns_sizes: (u16, u16, u16, u16) = (4, 8, 3, 11);
         let mut items: Vec<Row> = self
             .list
             .items
             .iter()
-            .map(|t| {
-                columns_sizes.0 = columns_sizes.0.max(t.name.len() as u16);
+            .enumerate()
+            .map(|(index, t)| {
+                let tree = self.tree_info(index);
+                let tags_len: usize = t.tags.iter().map(|tag| tag.len() + 3).sum();
+                columns_sizes.0 = columns_sizes
+                    .0
+                    .max((tree.width() + t.name.len() + tags_len) as u16);
                 columns_sizes.1 = columns_sizes.1.max(t.priority.to_string().len() as u16);
-                columns_sizes.2 = columns_sizes.2.max(t.description.len() as u16);
-                t.to_row(&self.config, &self.highlighted_item, None, None)
+                columns_sizes.2 = columns_sizes.2.max(t.due_text().chars().count() as u16);
+                let description = t.description.lines().next().unwrap_or_default();
+                columns_sizes.3 = columns_sizes.3.max(description.chars().count() as u16 + 2);
+                t.to_row(
+                    &self.config,
+                    tree,
+                    &self.highlighted_item,
+                    None,
+                    &self.search,
+                )
             })
             .collect();
 
-        let cursor_offset = if matches!(self.mode, Mode::EditDescription(..)) {
-            columns_sizes.2 = columns_sizes.2.max(self.input.len() as u16);
-            9 + columns_sizes.0 + columns_sizes.1
-        } else {
-            columns_sizes.0 = columns_sizes.0.max(self.input.len() as u16);
-            7
+        // the edited cell gets the room the other columns leave, the input scrolls within it.
+        let room = |others: u16| area.width.saturating_sub(13 + others);
+        let (edited, cursor_offset) = match self.mode {
+            Mode::EditDescription(..) => {
+                let room = room(columns_sizes.0 + columns_sizes.1 + columns_sizes.2);
+                let (line, cursor) = self.input.visible(room);
+                columns_sizes.3 = columns_sizes.3.max(line.width() as u16 + 1).min(room);
+                (
+                    line,
+                    12 + columns_sizes.0 + columns_sizes.1 + columns_sizes.2 + cursor,
+                )
+            }
+            Mode::EditDueDate(..) | Mode::EditRecurrence(..) => {
+                let room = room(columns_sizes.0 + columns_sizes.1 + columns_sizes.3);
+                let (line, cursor) = self.input.visible(room);
+                columns_sizes.2 = columns_sizes.2.max(line.width() as u16 + 1).min(room);
+                (line, 11 + columns_sizes.0 + columns_sizes.1 + cursor)
+            }
+            Mode::Insert(..) | Mode::Edit(..) => {
+                let prefix_width = match self.mode {
+                    Mode::Edit(target) => self.tree_info(target).width(),
+                    _ => self.insert_position.2 * 2 + 2,
+                } as u16;
+                let room = room(prefix_width + columns_sizes.1 + columns_sizes.2 + columns_sizes.3);
+                let (line, cursor) = self.input.visible(room);
+                columns_sizes.0 = columns_sizes
+                    .0
+                    .max(prefix_width + line.width() as u16 + 1)
+                    .min(prefix_width + room);
+                (line, 9 + prefix_width + cursor)
+            }
+            Mode::Search | Mode::FilterTags | Mode::Normal => (Line::default(), 0),
         };
 
         match self.mode {
@@ -578,9 +1199,22 @@ impl Component for TasksComponent {
                     target,
                     Row::new(vec![
                         Cell::from(" ☐"),
-                        Cell::from(self.input.clone()),
+                        Cell::from(Line::from(
+                            [
+                                vec![Span::raw(
+                                    TreeInfo {
+                                        depth: self.insert_position.2,
+                                        ..Default::default()
+                                    }
+                                    .prefix(),
+                                )],
+                                edited.spans,
+                            ]
+                            .concat(),
+                        )),
                         Cell::from(Text::raw("3").alignment(Alignment::Center)),
                         Cell::default(),
+                        Cell::default(),
                     ]),
                 );
                 self.list.state.select(Some(target));
@@ -588,18 +1222,30 @@ impl Component for TasksComponent {
             Mode::Edit(target) => {
                 items[target] = self.list.items[target].to_row(
                     &self.config,
+                    self.tree_info(target),
                     &self.highlighted_item,
-                    Some(self.input.clone()),
-                    None,
+                    Some(EditedCell::Name(edited)),
+                    &self.search,
                 );
                 self.list.state.select(Some(target));
             }
             Mode::EditDescription(target) => {
                 items[target] = self.list.items[target].to_row(
                     &self.config,
+                    self.tree_info(target),
                     &self.highlighted_item,
-                    None,
-                    Some(self.input.clone()),
+                    Some(EditedCell::Description(edited)),
+                    &self.search,
+                );
+                self.list.state.select(Some(target));
+            }
+            Mode::EditDueDate(target) | Mode::EditRecurrence(target) => {
+                items[target] = self.list.items[target].to_row(
+                    &self.config,
+                    self.tree_info(target),
+                    &self.highlighted_item,
+                    Some(EditedCell::DueDate(edited)),
+                    &self.search,
                 );
                 self.list.state.select(Some(target));
             }
@@ -607,10 +1253,11 @@ impl Component for TasksComponent {
         }
 
         let widths = [
-            Constraint::Length(3),
+            Constraint::Length(5),
             Constraint::Length(columns_sizes.0),
             Constraint::Length(columns_sizes.1),
             Constraint::Length(columns_sizes.2),
+            Constraint::Length(columns_sizes.3),
         ];
 
         let table = Table::new(items, widths)
@@ -619,18 +1266,26 @@ impl Component for TasksComponent {
             .highlight_symbol(">>")
             .highlight_spacing(HighlightSpacing::Always)
             .header(
-                Row::new(vec!["", "Name", "Priority", "Description"])
+                Row::new(vec!["", "Name", "Priority", "Due", "Description"])
                     .style(block_style)
                     .add_modifier(Modifier::REVERSED)
                     .add_modifier(Modifier::BOLD),
             );
 
         frame.render_stateful_widget(table, area, &mut self.list.state);
-        if let Mode::Insert(line) | Mode::Edit(line) | Mode::EditDescription(line) = self.mode {
-            frame.set_cursor(
-                area.x + self.character_index as u16 + cursor_offset,
-                area.y + line as u16 + 2,
-            );
+        if let Mode::Insert(line)
+        | Mode::Edit(line)
+        | Mode::EditDescription(line)
+        | Mode::EditDueDate(line)
+        | Mode::EditRecurrence(line) = self.mode
+        {
+            frame.set_cursor(area.x + cursor_offset, area.y + line as u16 + 2);
+        } else if self.mode == Mode::Search {
+            // the query is typed in the title, right after `Tasks /`.
+            frame.set_cursor(area.x + 8 + typed_cursor, area.y);
+        } else if self.mode == Mode::FilterTags {
+            // the tags are typed in the title, right after `Tasks tags: `.
+            frame.set_cursor(area.x + 13 + typed_cursor, area.y);
         }
 
         Ok(())
@@ -0,0 +1,366 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Single line text field shared by the components taking text, the cursor moves by graphemes so
+/// any text can be typed.
+#[derive(Debug, Default, Clone)]
+pub struct TextInput {
+    value: String,
+    /// the position of the cursor, in graphemes.
+    cursor: usize,
+    /// the first column shown, so the cursor stays in view when the text is too long.
+    offset: usize,
+    /// whether escape goes to a vim like normal mode instead of leaving the field.
+    vim_mode: bool,
+    normal: bool,
+}
+
+/// what a key did to the field.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InputResponse {
+    /// the key is not an editing key, the component may use it.
+    Ignored,
+    /// the key moved the cursor or changed mode.
+    Handled,
+    /// the key changed the text.
+    Edited,
+}
+
+impl TextInput {
+    pub fn set_vim_mode(&mut self, vim_mode: bool) {
+        self.vim_mode = vim_mode;
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// replaces the text and puts the cursor at its end, ready to type.
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.cursor = self.len();
+        self.offset = 0;
+        self.normal = false;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_value("");
+    }
+
+    fn len(&self) -> usize {
+        self.value.graphemes(true).count()
+    }
+
+    /// the byte index of the grapheme at `index`, the length of the text past the end.
+    fn byte_index(&self, index: usize) -> usize {
+        self.value
+            .grapheme_indices(true)
+            .nth(index)
+            .map_or(self.value.len(), |(byte, _)| byte)
+    }
+
+    fn is_blank(&self, index: usize) -> bool {
+        self.value
+            .graphemes(true)
+            .nth(index)
+            .is_some_and(|grapheme| grapheme.chars().all(char::is_whitespace))
+    }
+
+    /// the start of the word before the cursor, the blanks before the cursor are skipped.
+    fn previous_word(&self) -> usize {
+        let mut index = self.cursor;
+        while index > 0 && self.is_blank(index - 1) {
+            index -= 1;
+        }
+        while index > 0 && !self.is_blank(index - 1) {
+            index -= 1;
+        }
+        index
+    }
+
+    /// the end of the word after the cursor, the blanks after the cursor are skipped.
+    fn next_word_end(&self) -> usize {
+        let len = self.len();
+        let mut index = self.cursor;
+        while index < len && self.is_blank(index) {
+            index += 1;
+        }
+        while index < len && !self.is_blank(index) {
+            index += 1;
+        }
+        index
+    }
+
+    /// the start of the word after the cursor.
+    fn next_word_start(&self) -> usize {
+        let len = self.len();
+        let mut index = self.cursor;
+        while index < len && !self.is_blank(index) {
+            index += 1;
+        }
+        while index < len && self.is_blank(index) {
+            index += 1;
+        }
+        index
+    }
+
+    fn insert(&mut self, c: char) {
+        let byte = self.byte_index(self.cursor);
+        self.value.insert(byte, c);
+        // a combining character or a joiner merges with the graphemes around it instead of
+        // adding one, the cursor goes right after what was typed.
+        self.cursor = self.value[..byte + c.len_utf8()].graphemes(true).count();
+    }
+
+    /// removes the graphemes from `start` to `end` and puts the cursor at `start`.
+    fn delete(&mut self, start: usize, end: usize) -> InputResponse {
+        if start >= end {
+            return InputResponse::Handled;
+        }
+        let range = self.byte_index(start)..self.byte_index(end);
+        self.value.replace_range(range, "");
+        self.cursor = start;
+        InputResponse::Edited
+    }
+
+    fn move_to(&mut self, index: usize) -> InputResponse {
+        self.cursor = index;
+        InputResponse::Handled
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputResponse {
+        if self.normal {
+            return self.handle_normal_key(key);
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let len = self.len();
+        match key.code {
+            KeyCode::Esc if self.vim_mode => {
+                self.normal = true;
+                // the cursor stands on a character in normal mode, like in vim.
+                self.cursor = self.cursor.saturating_sub(1);
+                InputResponse::Handled
+            }
+            KeyCode::Char('w') if ctrl => self.delete(self.previous_word(), self.cursor),
+            KeyCode::Backspace if ctrl || alt => self.delete(self.previous_word(), self.cursor),
+            KeyCode::Char('u') if ctrl => self.delete(0, self.cursor),
+            KeyCode::Char('k') if ctrl => self.delete(self.cursor, len),
+            KeyCode::Char('a') if ctrl => self.move_to(0),
+            KeyCode::Char('e') if ctrl => self.move_to(len),
+            KeyCode::Char('b') if alt => self.move_to(self.previous_word()),
+            KeyCode::Char('f') if alt => self.move_to(self.next_word_end()),
+            KeyCode::Char('d') if alt => self.delete(self.cursor, self.next_word_end()),
+            KeyCode::Left if ctrl || alt => self.move_to(self.previous_word()),
+            KeyCode::Right if ctrl || alt => self.move_to(self.next_word_end()),
+            KeyCode::Char(c) if !ctrl && !alt => {
+                self.insert(c);
+                InputResponse::Edited
+            }
+            KeyCode::Backspace if self.cursor > 0 => self.delete(self.cursor - 1, self.cursor),
+            KeyCode::Delete => self.delete(self.cursor, (self.cursor + 1).min(len)),
+            KeyCode::Left => self.move_to(self.cursor.saturating_sub(1)),
+            KeyCode::Right => self.move_to((self.cursor + 1).min(len)),
+            KeyCode::Home => self.move_to(0),
+            KeyCode::End => self.move_to(len),
+            _ => InputResponse::Ignored,
+        }
+    }
+
+    fn handle_normal_key(&mut self, key: KeyEvent) -> InputResponse {
+        let len = self.len();
+        let last = len.saturating_sub(1);
+        let response = match key.code {
+            KeyCode::Char('h') | KeyCode::Left => self.move_to(self.cursor.saturating_sub(1)),
+            KeyCode::Char('l') | KeyCode::Right => self.move_to((self.cursor + 1).min(last)),
+            KeyCode::Char('0') | KeyCode::Char('^') | KeyCode::Home => self.move_to(0),
+            KeyCode::Char('$') | KeyCode::End => self.move_to(last),
+            KeyCode::Char('b') => self.move_to(self.previous_word()),
+            KeyCode::Char('w') => self.move_to(self.next_word_start().min(last)),
+            KeyCode::Char('e') => {
+                // looking from the next character, a cursor ending a word goes to the next one.
+                self.cursor = (self.cursor + 1).min(len);
+                let end = self.next_word_end();
+                self.move_to(end.saturating_sub(1).min(last))
+            }
+            KeyCode::Char('x') | KeyCode::Delete => {
+                self.delete(self.cursor, (self.cursor + 1).min(len))
+            }
+            KeyCode::Char('X') => self.delete(self.cursor.saturating_sub(1), self.cursor),
+            KeyCode::Char('D') => self.delete(self.cursor, len),
+            KeyCode::Char('i') => {
+                self.normal = false;
+                InputResponse::Handled
+            }
+            KeyCode::Char('a') => {
+                self.normal = false;
+                self.move_to((self.cursor + 1).min(len))
+            }
+            KeyCode::Char('I') => {
+                self.normal = false;
+                self.move_to(0)
+            }
+            KeyCode::Char('A') => {
+                self.normal = false;
+                self.move_to(len)
+            }
+            KeyCode::Char('C') => {
+                self.normal = false;
+                self.delete(self.cursor, len)
+            }
+            KeyCode::Char('S') => {
+                self.normal = false;
+                self.delete(0, len)
+            }
+            // the other characters are not typed in normal mode.
+            KeyCode::Char(_) => InputResponse::Handled,
+            _ => InputResponse::Ignored,
+        };
+        // the cursor stays on the last character after a deletion.
+        if self.normal {
+            self.cursor = self.cursor.min(self.len().saturating_sub(1));
+        }
+        response
+    }
+
+    /// the part of the text fitting in `width` columns around the cursor, and the column of the
+    /// cursor in it. in normal mode the character under the cursor is reversed.
+    pub fn visible(&mut self, width: u16) -> (Line<'static>, u16) {
+        let width = width.max(1) as usize;
+        let graphemes: Vec<&str> = self.value.graphemes(true).collect();
+        let cursor_column: usize = graphemes[..self.cursor].iter().map(|g| g.width()).sum();
+        let text_width: usize = graphemes.iter().map(|g| g.width()).sum();
+        // the offset follows the cursor, and comes back when the end of the text is in view.
+        if cursor_column < self.offset {
+            self.offset = cursor_column;
+        } else if cursor_column >= self.offset + width {
+            self.offset = cursor_column + 1 - width;
+        }
+        self.offset = self.offset.min((text_width + 1).saturating_sub(width));
+
+        let mut spans = Vec::new();
+        let mut column = 0;
+        for (index, grapheme) in graphemes.iter().enumerate() {
+            let start = column;
+            column += grapheme.width();
+            if start < self.offset {
+                continue;
+            }
+            if column > self.offset + width {
+                break;
+            }
+            if self.normal && index == self.cursor {
+                spans.push(Span::styled(
+                    grapheme.to_string(),
+                    Style::default().add_modifier(Modifier::REVERSED),
+                ));
+            } else {
+                spans.push(Span::raw(grapheme.to_string()));
+            }
+        }
+        (Line::from(spans), (cursor_column - self.offset) as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(input: &mut TextInput, text: &str) {
+        for c in text.chars() {
+            input.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    fn key(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) -> InputResponse {
+        input.handle_key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn test_non_ascii_input() {
+        let mut input = TextInput::default();
+        type_text(&mut input, "café 👍🏽");
+        key(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        key(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        type_text(&mut input, "é");
+        assert_eq!(input.value(), "caféé 👍🏽");
+        key(&mut input, KeyCode::End, KeyModifiers::NONE);
+        key(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "caféé ");
+        key(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        key(&mut input, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(input.value(), "aféé ");
+        // a combining accent joins the letter before it.
+        type_text(&mut input, "e\u{301}");
+        key(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "aféé ");
+        // a zero width joiner between two emoji makes them a single grapheme.
+        input.clear();
+        type_text(&mut input, "👍👍");
+        key(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        type_text(&mut input, "\u{200d}");
+        assert_eq!(input.value(), "👍\u{200d}👍");
+        assert_eq!(input.cursor, 1);
+        type_text(&mut input, "!");
+        assert_eq!(input.value(), "👍\u{200d}👍!");
+    }
+
+    #[test]
+    fn test_word_editing() {
+        let mut input = TextInput::default();
+        type_text(&mut input, "buy some  milk");
+        key(&mut input, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(input.cursor, 10);
+        key(&mut input, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(input.value(), "buy milk");
+        key(&mut input, KeyCode::Char('f'), KeyModifiers::ALT);
+        assert_eq!(input.cursor, 8);
+        key(&mut input, KeyCode::Left, KeyModifiers::CONTROL);
+        key(&mut input, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(input.value(), "milk");
+        assert_eq!(
+            key(&mut input, KeyCode::Enter, KeyModifiers::NONE),
+            InputResponse::Ignored
+        );
+    }
+
+    #[test]
+    fn test_vim_mode() {
+        let mut input = TextInput::default();
+        input.set_vim_mode(true);
+        type_text(&mut input, "buy some milk");
+        key(&mut input, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(input.normal);
+        type_text(&mut input, "bbx");
+        assert_eq!(input.value(), "buy ome milk");
+        type_text(&mut input, "0wiS");
+        assert_eq!(input.value(), "buy Some milk");
+        assert!(!input.normal);
+        key(&mut input, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(
+            key(&mut input, KeyCode::Esc, KeyModifiers::NONE),
+            InputResponse::Ignored
+        );
+        type_text(&mut input, "$D");
+        assert_eq!(input.value(), "buy Some mil");
+    }
+
+    #[test]
+    fn test_horizontal_scroll() {
+        let mut input = TextInput::default();
+        type_text(&mut input, "0123456789");
+        let (line, cursor) = input.visible(5);
+        assert_eq!(line.to_string(), "6789");
+        assert_eq!(cursor, 4);
+        key(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        let (line, cursor) = input.visible(5);
+        assert_eq!(line.to_string(), "01234");
+        assert_eq!(cursor, 0);
+    }
+}
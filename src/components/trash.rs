@@ -0,0 +1,188 @@
+use super::{centered_rect, Component};
+use crate::action::Action;
+use crate::app::ComponentId;
+use crate::config::{Config, StyleName};
+use crate::structs::{format_date, TrashContent, TrashItem};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Popup listing the removed tasks and workspaces, they can be restored or purged for good.
+#[derive(Debug)]
+pub struct Trash {
+    is_focused: bool,
+    objective: ComponentId,
+    items: Vec<TrashItem>,
+    state: ListState,
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+}
+
+impl Trash {
+    pub fn new() -> Self {
+        Self {
+            is_focused: false,
+            objective: ComponentId::Workspaces,
+            items: Vec::new(),
+            state: ListState::default(),
+            command_tx: None,
+            config: Config::default(),
+        }
+    }
+
+    fn selected_item(&self) -> Option<&TrashItem> {
+        self.state
+            .selected()
+            .and_then(|index| self.items.get(index))
+    }
+
+    fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<()> {
+        let command_tx = self.command_tx.as_ref().unwrap();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                command_tx.send(Action::ExitTrash(self.objective))?;
+            }
+            KeyCode::Down | KeyCode::Char('j') if !self.items.is_empty() => {
+                let selected = self.state.selected().map_or(0, |selected| selected + 1);
+                self.state.select(Some(selected % self.items.len()));
+            }
+            KeyCode::Up | KeyCode::Char('k') if !self.items.is_empty() => {
+                let selected = self.state.selected().unwrap_or_default();
+                self.state
+                    .select(Some((selected + self.items.len() - 1) % self.items.len()));
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.state.select(Some(0)),
+            KeyCode::End | KeyCode::Char('G') => {
+                self.state.select(Some(self.items.len().saturating_sub(1)));
+            }
+            KeyCode::Enter | KeyCode::Char('r') => {
+                if let Some(item) = self.selected_item() {
+                    command_tx.send(Action::RestoreTrashItem(item.id))?;
+                }
+            }
+            KeyCode::Delete | KeyCode::Char('d') => {
+                if let Some(item) = self.selected_item() {
+                    command_tx.send(Action::PurgeTrashItem(item.id))?;
+                }
+            }
+            KeyCode::Char('D') if !self.items.is_empty() => {
+                command_tx.send(Action::EmptyTrash)?;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+}
+
+impl Component for Trash {
+    fn focus(&mut self, focus: bool) -> Result<()> {
+        self.is_focused = focus;
+        if focus {
+            self.command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::RequestTrashData)?;
+        }
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::SetupTrash(component_id) => {
+                self.objective = component_id;
+                self.state.select(Some(0));
+            }
+            Action::NewTrashData(items) => {
+                self.items = items;
+                // the selection stays in place when the selected item goes away.
+                let selected = self.state.selected().unwrap_or_default();
+                self.state
+                    .select(Some(selected.min(self.items.len().saturating_sub(1))));
+            }
+            Action::SendKeyEvent(key) => {
+                self.handle_insert_mode(key)?;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.is_focused {
+            return Ok(());
+        }
+        let block_style = self.config.styles[&StyleName::Highlight];
+        let selection_style = self.config.styles[&StyleName::Selected];
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title("Trash")
+            .title_bottom(
+                Line::from(" enter restore · d purge · D empty · esc close ").right_aligned(),
+            )
+            .style(block_style);
+
+        let name_width = self
+            .items
+            .iter()
+            .map(|item| item.name().chars().count())
+            .max()
+            .unwrap_or_default();
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|item| {
+                let (kind, place) = match &item.content {
+                    TrashContent::Task { workspace, .. } => ("task", format!("in {workspace}")),
+                    TrashContent::Workspace { .. } => ("workspace", String::new()),
+                };
+                let tasks = match item.task_count() {
+                    0 => String::new(),
+                    1 => "1 task".to_string(),
+                    count => format!("{count} tasks"),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{} ", format_date(&item.deleted_at))),
+                    Span::styled(
+                        format!("{kind:9} "),
+                        Style::default().add_modifier(Modifier::DIM),
+                    ),
+                    Span::styled(
+                        format!("{:name_width$} ", item.name()),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!("{place} {tasks}").trim().to_string()),
+                ]))
+                .style(Style::default())
+            })
+            .collect();
+
+        let area = centered_rect(70, 60, area);
+        frame.render_widget(Clear, area); //this clears out the background
+        if items.is_empty() {
+            let empty = Paragraph::new("The trash is empty")
+                .style(Style::default())
+                .block(block);
+            frame.render_widget(empty, area);
+            return Ok(());
+        }
+        let items = List::new(items)
+            .block(block)
+            .highlight_style(selection_style)
+            .highlight_symbol(">>")
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(items, area, &mut self.state);
+        Ok(())
+    }
+}
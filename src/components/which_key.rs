@@ -0,0 +1,136 @@
+use super::{help::action_name, Component};
+use crate::action::Action;
+use crate::app::Mode;
+use crate::config::{key_sequence_to_string, Config, KeyBindings, StyleName};
+use color_eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::{prelude::*, widgets::*};
+
+/// Popup listing the keys that can follow a partly typed key sequence.
+#[derive(Debug, Default)]
+pub struct WhichKey {
+    pending: Vec<KeyEvent>,
+    config: Config,
+}
+
+impl WhichKey {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// the rest of the sequences starting with `pending` and their actions, sorted by keys. the
+/// sequences are only typed in navigation, so the global and navigation keymaps are used.
+fn completions(keybindings: &KeyBindings, pending: &[KeyEvent]) -> Vec<(String, String)> {
+    let mut completions: Vec<(String, String)> = [Mode::Global, Mode::Navigation]
+        .iter()
+        .filter_map(|mode| keybindings.get(mode))
+        .flatten()
+        .filter(|(keys, _)| keys.len() > pending.len() && keys.starts_with(pending))
+        .map(|(keys, action)| {
+            (
+                key_sequence_to_string(&keys[pending.len()..]),
+                action_name(action),
+            )
+        })
+        .collect();
+    completions.sort();
+    completions.dedup();
+    completions
+}
+
+impl Component for WhichKey {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<()> {
+        if let Action::PendingKeys(keys) = action {
+            self.pending = keys;
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let block_style = self.config.styles[&StyleName::Highlight];
+        let completions = completions(&self.config.keybindings, &self.pending);
+        let keys_width = completions
+            .iter()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or_default();
+        let lines: Vec<Line> = completions
+            .iter()
+            .map(|(keys, action)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{keys:keys_width$}"),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("  "),
+                    Span::raw(action.clone()),
+                ])
+            })
+            .collect();
+
+        // drawn in the bottom right corner, over the tasks.
+        let title = key_sequence_to_string(&self.pending);
+        let width = lines
+            .iter()
+            .map(Line::width)
+            .chain([title.chars().count()])
+            .max()
+            .unwrap_or_default() as u16
+            + 2;
+        let width = width.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let area = Rect::new(area.right() - width, area.bottom() - height, width, height);
+        frame.render_widget(Clear, area); //this clears out the background
+
+        let popup = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .style(block_style),
+        );
+        frame.render_widget(popup, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::parse_key_sequence;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_completions() {
+        let mut keybindings = KeyBindings::default();
+        keybindings.insert(
+            Mode::Navigation,
+            HashMap::from([
+                (parse_key_sequence("<g><g>").unwrap(), Action::GoToBottom),
+                (parse_key_sequence("<g><t>").unwrap(), Action::GoToTop),
+                (parse_key_sequence("<j>").unwrap(), Action::GoDown),
+            ]),
+        );
+        keybindings.insert(Mode::Insert, HashMap::new());
+
+        let pending = parse_key_sequence("<g>").unwrap();
+        assert_eq!(
+            completions(&keybindings, &pending),
+            vec![
+                ("<g>".to_string(), "Go to bottom".to_string()),
+                ("<t>".to_string(), "Go to top".to_string()),
+            ]
+        );
+        let pending = parse_key_sequence("<j>").unwrap();
+        assert_eq!(completions(&keybindings, &pending), Vec::new());
+    }
+}
@@ -1,13 +1,17 @@
-use super::Component;
+use super::{
+    search::{highlight_matches, is_match, next_match},
+    text_input::{InputResponse, TextInput},
+    Component,
+};
 use crate::{
     action::Action,
     config::{Config, StyleName},
     structs::*,
 };
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{prelude::*, widgets::*};
-use std::cmp::min;
+use std::{cmp::min, collections::HashMap};
 use tokio::sync::mpsc::UnboundedSender;
 
 #[derive(Default, Debug)]
@@ -15,13 +19,18 @@ pub struct WorkspacesComponent {
     command_tx: Option<UnboundedSender<Action>>,
     config: Config,
     list: WorkspacesList,
-    input: String, // save the currently edited text
-    character_index: usize,
+    input: TextInput, // save the currently edited text
     mode: Mode,
     sorter: WorkspaceSorter,
     is_focused: bool,
     highlighted_item: (Option<usize>, String),
     to_be_selected: Option<usize>, // to save the index of the new element to be selected.
+    search: String,
+    search_origin: Option<usize>,
+    /// the number of tasks of the workspaces whose tasks were loaded.
+    task_counts: HashMap<i32, usize>,
+    /// where the list was last drawn, to find the clicked workspace.
+    area: Rect,
 }
 
 #[derive(Default, PartialEq, Eq, Debug)]
@@ -30,6 +39,7 @@ enum Mode {
     Normal,
     Insert(usize),
     Edit(usize),
+    Search,
 }
 
 #[derive(Default, Debug)]
@@ -107,32 +117,52 @@ impl WorkspacesComponent {
         Ok(())
     }
 
+    fn matches(&self) -> Vec<bool> {
+        self.list
+            .items
+            .iter()
+            .map(|w| is_match(&w.name, &self.search))
+            .collect()
+    }
+
+    /// selects the next workspace matching the search, or the previous one when `backward` is set.
+    fn select_match(&mut self, from: Option<usize>, backward: bool) -> Result<()> {
+        if let Some(index) = next_match(&self.matches(), from, backward) {
+            self.list.state.select(Some(index));
+            self.send_workspace_id()?;
+        }
+        Ok(())
+    }
+
     fn submit(&mut self) -> Result<()> {
         let command_tx = self.command_tx.as_ref().unwrap();
         match self.mode {
+            Mode::Search => {
+                command_tx.send(Action::LeaveInsertMode)?;
+                self.input.clear();
+                self.mode = Mode::Normal;
+            }
             Mode::Insert(target) => {
                 let w = AddWorkspace {
-                    name: self.input.trim().to_string(),
+                    name: self.input.value().trim().to_string(),
                     order: Some(target),
                 };
                 command_tx.send(Action::AddWorkspace(w))?;
                 command_tx.send(Action::LeaveInsertMode)?;
                 self.to_be_selected = Some(target);
                 self.input.clear();
-                self.character_index = 0;
                 self.mode = Mode::Normal;
             }
             Mode::Edit(target) => {
                 let w = UpdateWorkspace {
                     id: self.list.items[target].id,
-                    name: Some(self.input.trim().to_string()),
+                    name: Some(self.input.value().trim().to_string()),
                     ..Default::default()
                 };
                 command_tx.send(Action::UpdateWorkspace(w))?;
                 command_tx.send(Action::LeaveInsertMode)?;
                 self.to_be_selected = Some(target);
                 self.input.clear();
-                self.character_index = 0;
                 self.mode = Mode::Normal;
             }
             _ => unreachable!(),
@@ -140,38 +170,61 @@ impl WorkspacesComponent {
         Ok(())
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        if !self.area.contains(Position::new(mouse.column, mouse.row)) {
+            return Ok(());
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if !self.is_focused {
+                    self.command_tx
+                        .as_ref()
+                        .unwrap()
+                        .send(Action::FocusOnWorkspaces)?;
+                }
+                // the items start under the border.
+                let index =
+                    mouse.row.saturating_sub(self.area.y + 1) as usize + self.list.state.offset();
+                if mouse.row > self.area.y
+                    && mouse.row + 1 < self.area.bottom()
+                    && index < self.list.items.len()
+                {
+                    self.list.state.select(Some(index));
+                    self.send_workspace_id()?;
+                }
+            }
+            MouseEventKind::ScrollDown => self.select_next()?,
+            MouseEventKind::ScrollUp => self.select_previous()?,
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<()> {
         let command_tx = self.command_tx.as_ref().unwrap();
-        match key.code {
-            KeyCode::Char(c) => {
-                self.input.insert(self.character_index, c);
-                self.character_index += 1;
-            }
-            KeyCode::Backspace => {
-                if self.character_index > 0 {
-                    self.character_index -= 1;
-                    self.input.remove(self.character_index);
-                }
+        if self.input.handle_key(key) != InputResponse::Ignored {
+            // searching as you type, starting from the workspace selected when the search began.
+            if self.mode == Mode::Search && self.search != self.input.value() {
+                self.search = self.input.value().to_string();
+                self.select_match(self.search_origin.and_then(|i| i.checked_sub(1)), false)?;
             }
+            return Ok(());
+        }
+        match key.code {
             KeyCode::Enter => {
                 self.submit()?;
+                return Ok(());
             }
             KeyCode::Esc => {
                 command_tx.send(Action::LeaveInsertMode)?;
+                if self.mode == Mode::Search {
+                    self.search.clear();
+                    self.list.state.select(self.search_origin);
+                    self.send_workspace_id()?;
+                }
                 self.input.clear();
-                self.character_index = 0;
                 self.mode = Mode::Normal;
             }
-            KeyCode::Left => {
-                if self.character_index > 0 {
-                    self.character_index -= 1;
-                }
-            }
-            KeyCode::Right => {
-                if self.character_index < self.input.len() {
-                    self.character_index += 1;
-                }
-            }
             _ => {}
         };
         Ok(())
@@ -180,6 +233,7 @@ impl WorkspacesComponent {
 
 impl Component for WorkspacesComponent {
     fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.input.set_vim_mode(config.config.vim_input);
         self.config = config;
         Ok(())
     }
@@ -225,12 +279,21 @@ impl Component for WorkspacesComponent {
 
                 self.send_workspace_id()?;
             }
+            Action::NewTasksData((tasks, workspace_id)) => {
+                self.task_counts.insert(workspace_id, tasks.len());
+            }
             Action::GoUp => {
                 self.select_previous()?;
             }
             Action::GoDown => {
                 self.select_next()?;
             }
+            Action::GoRight => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::FocusOnTasks)?;
+            }
             Action::GoToTop => {
                 self.select_top()?;
             }
@@ -245,6 +308,9 @@ impl Component for WorkspacesComponent {
                 );
                 self.handle_insert_mode(key)?;
             }
+            Action::SendMouseEvent(mouse) if self.mode == Mode::Normal => {
+                self.handle_mouse_event(mouse)?;
+            }
             Action::AddItemAfter => {
                 if let Some(selected) = self.list.state.selected() {
                     self.mode = Mode::Insert(selected + 1);
@@ -263,17 +329,56 @@ impl Component for WorkspacesComponent {
             }
             Action::DeleteItem => {
                 if let Some(selected) = self.list.state.selected() {
-                    command_tx.send(Action::RemoveWorkspace(self.list.items[selected].id))?;
+                    let workspace = &self.list.items[selected];
+                    let remove = Action::RemoveWorkspace(workspace.id);
+                    match self.task_counts.get(&workspace.id) {
+                        Some(&count) if count > 0 && self.config.config.confirm_delete => {
+                            let tasks = if count == 1 { "task" } else { "tasks" };
+                            command_tx.send(Action::AskConfirmation(
+                                format!(
+                                    "Delete the workspace {} and its {count} {tasks}? They can be restored from the trash.",
+                                    workspace.name
+                                ),
+                                Box::new(remove),
+                            ))?;
+                        }
+                        _ => command_tx.send(remove)?,
+                    }
                 }
             }
             Action::EditItem => {
                 if let Some(selected) = self.list.state.selected() {
                     self.mode = Mode::Edit(selected);
-                    self.input.clone_from(&self.list.items[selected].name);
-                    self.character_index = self.input.len();
+                    self.input.set_value(&self.list.items[selected].name);
                     command_tx.send(Action::EnterInsertMode)?;
                 }
             }
+            Action::Search => {
+                self.mode = Mode::Search;
+                self.input.set_value(&self.search);
+                self.search_origin = self.list.state.selected();
+                command_tx.send(Action::EnterInsertMode)?;
+            }
+            Action::NextMatch => {
+                self.select_match(self.list.state.selected(), false)?;
+            }
+            Action::PreviousMatch => {
+                self.select_match(self.list.state.selected(), true)?;
+            }
+            Action::Cancel => {
+                self.search.clear();
+            }
+            Action::JumpToTask(task) => {
+                if let Some(index) = self
+                    .list
+                    .items
+                    .iter()
+                    .position(|w| w.id == task.workspace_id)
+                {
+                    self.list.state.select(Some(index));
+                    self.send_workspace_id()?;
+                }
+            }
             Action::HighlightWorkspace(name) => {
                 self.highlighted_item = (Some(9), name);
             }
@@ -341,16 +446,45 @@ impl Component for WorkspacesComponent {
     }
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        self.area = area;
         let selected_style = self.config.styles[&StyleName::Selected];
         let error_style = self.config.styles[&StyleName::Error];
+        let match_style = self.config.styles[&StyleName::SearchMatch];
         let block_style = if self.is_focused {
             self.config.styles[&StyleName::Highlight]
         } else {
             Style::default()
         };
 
+        // the query is typed in the title.
+        let (typed, typed_cursor) = if self.mode == Mode::Search {
+            let (line, cursor) = self.input.visible(area.width.saturating_sub(20));
+            (line.to_string(), cursor)
+        } else {
+            (String::new(), 0)
+        };
+        // the search indicator shows the query and the position of the selection among matches.
+        let title = if self.mode == Mode::Search || !self.search.is_empty() {
+            let matches = self.matches();
+            let total = matches.iter().filter(|m| **m).count();
+            let current = match self.list.state.selected() {
+                Some(selected) if matches.get(selected) == Some(&true) => {
+                    (matches[..=selected].iter().filter(|m| **m).count()).to_string()
+                }
+                _ => "-".to_string(),
+            };
+            let search = if self.mode == Mode::Search {
+                &typed
+            } else {
+                &self.search
+            };
+            format!("Workspaces /{} [{}/{}]", search, current, total)
+        } else {
+            "Workspaces".to_string()
+        };
+
         let block = Block::default()
-            .title("Workspaces")
+            .title(title)
             .border_style(block_style)
             .border_type(BorderType::Rounded)
             .borders(Borders::ALL);
@@ -363,18 +497,24 @@ impl Component for WorkspacesComponent {
                 if self.highlighted_item.0.is_some() && self.highlighted_item.1 == w.name {
                     ListItem::new(Line::from(w.name.clone()).style(error_style))
                 } else {
-                    ListItem::new(Line::from(w.name.clone()))
+                    ListItem::new(Line::from(highlight_matches(
+                        &w.name,
+                        &self.search,
+                        match_style,
+                    )))
                 }
             })
             .collect();
 
+        // the input scrolls within the list, past the borders and the highlight symbol.
+        let (edited, cursor) = self.input.visible(area.width.saturating_sub(5));
         match self.mode {
             Mode::Insert(target) => {
-                items.insert(target, ListItem::new(self.input.clone()));
+                items.insert(target, ListItem::new(edited));
                 self.list.state.select(Some(target));
             }
             Mode::Edit(target) => {
-                items[target] = ListItem::new(self.input.clone());
+                items[target] = ListItem::new(edited);
                 self.list.state.select(Some(target));
             }
             _ => {}
@@ -388,10 +528,10 @@ impl Component for WorkspacesComponent {
 
         frame.render_stateful_widget(items, area, &mut self.list.state);
         if let Mode::Edit(line) | Mode::Insert(line) = self.mode {
-            frame.set_cursor(
-                area.x + 3 + self.character_index as u16,
-                area.y + line as u16 + 1,
-            );
+            frame.set_cursor(area.x + 3 + cursor, area.y + line as u16 + 1);
+        } else if self.mode == Mode::Search {
+            // the query is typed in the title, right after `Workspaces /`.
+            frame.set_cursor(area.x + 13 + typed_cursor, area.y);
         }
         Ok(())
     }
@@ -8,15 +8,44 @@ use serde::{de::Deserializer, Deserialize, Serialize};
 use std::{collections::HashMap, env, path::PathBuf};
 use tracing::error;
 
-use crate::{action::Action, app::Mode};
+use crate::{action::Action, app::Mode, layout::PaneLayout, structs::Task};
 
 const CONFIG: &str = include_str!("../.config/config.toml");
 
+/// stands for the leader key in the keybindings until it is replaced by the configured key.
+const LEADER: KeyEvent = KeyEvent::new(KeyCode::Null, KeyModifiers::empty());
+
 #[allow(dead_code)]
-#[derive(Clone, Debug, Deserialize, Default)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
-    #[serde(default)]
     pub data_dir: PathBuf,
+    /// how long a partly typed key sequence waits for its next key, in milliseconds.
+    pub sequence_timeout: u64,
+    /// the key written `<leader>` in the keybindings.
+    pub leader: String,
+    /// whether deleting a workspace that still has tasks asks first.
+    pub confirm_delete: bool,
+    /// whether escape in a text field goes to a vim like normal mode before leaving it.
+    pub vim_input: bool,
+    /// whether the mouse can select, toggle and move the items.
+    pub mouse: bool,
+    #[serde(flatten)]
+    pub layout: PaneLayout,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            data_dir: PathBuf::default(),
+            sequence_timeout: 1000,
+            leader: "<space>".to_string(),
+            confirm_delete: true,
+            vim_input: false,
+            mouse: true,
+            layout: PaneLayout::default(),
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -28,6 +57,8 @@ pub struct Config {
     pub keybindings: KeyBindings,
     #[serde(default)]
     pub styles: Styles,
+    #[serde(default)]
+    pub statuses: Statuses,
 }
 
 lazy_static! {
@@ -45,11 +76,28 @@ lazy_static! {
 impl Config {
     pub fn new() -> Result<Self, config::ConfigError> {
         let default_config: Config = toml::from_str(CONFIG).unwrap();
+        let default_layout = default_config.config.layout;
         let data_dir = get_data_dir();
         let config_dir = get_config_dir();
         let mut builder = config::Config::builder()
             .set_default("data_dir", data_dir.to_str().unwrap())?
-            .set_default("config_dir", config_dir.to_str().unwrap())?;
+            .set_default("config_dir", config_dir.to_str().unwrap())?
+            .set_default("sequence_timeout", default_config.config.sequence_timeout)?
+            .set_default("leader", default_config.config.leader.clone())?
+            .set_default("confirm_delete", default_config.config.confirm_delete)?
+            .set_default("vim_input", default_config.config.vim_input)?
+            .set_default("mouse", default_config.config.mouse)?
+            .set_default("layout", default_layout.orientation.to_string())?
+            .set_default(
+                "workspace_width",
+                default_layout.workspace_width.to_string(),
+            )?
+            .set_default(
+                "workspace_height",
+                default_layout.workspace_height.to_string(),
+            )?
+            .set_default("stack_below", default_layout.stack_below)?
+            .set_default("hide_workspaces", default_layout.hide_workspaces)?;
 
         let config_files = [
             ("config.json5", config::FileFormat::Json5),
@@ -85,9 +133,29 @@ impl Config {
         for (style_key, style) in default_config.styles.iter() {
             cfg.styles.entry(*style_key).or_insert(*style);
         }
+        // the statuses are a workflow, they are replaced as a whole.
+        if cfg.statuses.is_empty() {
+            cfg.statuses = default_config.statuses;
+        }
+        cfg.statuses
+            .validate()
+            .map_err(config::ConfigError::Message)?;
+        cfg.keybindings
+            .replace_leader(&cfg.config.leader)
+            .map_err(config::ConfigError::Message)?;
 
         Ok(cfg)
     }
+
+    /// the default configuration, used when the user configuration can not be loaded.
+    pub fn fallback() -> Self {
+        let mut cfg: Self = toml::from_str(CONFIG).unwrap();
+        cfg.config.data_dir = get_data_dir();
+        cfg.keybindings
+            .replace_leader(&cfg.config.leader)
+            .expect("the default leader is valid");
+        cfg
+    }
 }
 
 pub fn get_data_dir() -> PathBuf {
@@ -119,6 +187,29 @@ fn project_directory() -> Option<ProjectDirs> {
 #[derive(Clone, Debug, Default, Deref, DerefMut)]
 pub struct KeyBindings(pub HashMap<Mode, HashMap<Vec<KeyEvent>, Action>>);
 
+impl KeyBindings {
+    /// replaces `<leader>` in every key sequence by the `leader` key.
+    fn replace_leader(&mut self, leader: &str) -> Result<(), String> {
+        let leader = match parse_key_sequence(leader)?[..] {
+            [key] => key,
+            _ => return Err(format!("the leader `{leader}` must be a single key")),
+        };
+        for bindings in self.values_mut() {
+            *bindings = bindings
+                .drain()
+                .map(|(keys, action)| {
+                    let keys = keys
+                        .into_iter()
+                        .map(|key| if key == LEADER { leader } else { key })
+                        .collect();
+                    (keys, action)
+                })
+                .collect();
+        }
+        Ok(())
+    }
+}
+
 impl<'de> Deserialize<'de> for KeyBindings {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -131,11 +222,12 @@ impl<'de> Deserialize<'de> for KeyBindings {
             .map(|(mode, inner_map)| {
                 let converted_inner_map = inner_map
                     .into_iter()
-                    .map(|(key_str, cmd)| (parse_key_sequence(&key_str).unwrap(), cmd))
-                    .collect();
-                (mode, converted_inner_map)
+                    .map(|(key_str, cmd)| Ok((parse_key_sequence(&key_str)?, cmd)))
+                    .collect::<Result<_, String>>()?;
+                Ok((mode, converted_inner_map))
             })
-            .collect();
+            .collect::<Result<_, String>>()
+            .map_err(serde::de::Error::custom)?;
 
         Ok(KeyBindings(keybindings))
     }
@@ -210,6 +302,7 @@ fn parse_key_code_with_modifiers(
         "hyphen" => KeyCode::Char('-'),
         "minus" => KeyCode::Char('-'),
         "tab" => KeyCode::Tab,
+        "leader" => return Ok(LEADER),
         c if c.len() == 1 => {
             let mut c = c.chars().next().unwrap();
             if modifiers.contains(KeyModifiers::SHIFT) {
@@ -222,7 +315,6 @@ fn parse_key_code_with_modifiers(
     Ok(KeyEvent::new(c, modifiers))
 }
 
-#[allow(dead_code)]
 pub fn key_event_to_string(key_event: &KeyEvent) -> String {
     let char;
     let key_code = match key_event.code {
@@ -241,7 +333,7 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
         KeyCode::Delete => "delete",
         KeyCode::Insert => "insert",
         KeyCode::F(c) => {
-            char = format!("f({c})");
+            char = format!("f{c}");
             &char
         }
         KeyCode::Char(' ') => "space",
@@ -250,7 +342,7 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
             &char
         }
         KeyCode::Esc => "esc",
-        KeyCode::Null => "",
+        KeyCode::Null => "leader",
         KeyCode::CapsLock => "",
         KeyCode::Menu => "",
         KeyCode::ScrollLock => "",
@@ -286,6 +378,13 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
     key
 }
 
+/// writes a key sequence the way it is written in the config, `<ctrl-shift-k>` or `<g><g>`.
+pub fn key_sequence_to_string(keys: &[KeyEvent]) -> String {
+    keys.iter()
+        .map(|key| format!("<{}>", key_event_to_string(key).to_lowercase()))
+        .collect()
+}
+
 pub fn parse_key_sequence(raw: &str) -> Result<Vec<KeyEvent>, String> {
     if raw.chars().filter(|c| *c == '>').count() != raw.chars().filter(|c| *c == '<').count() {
         return Err(format!("Unable to parse `{}`", raw));
@@ -321,7 +420,11 @@ pub enum StyleName {
     Selected,
     Error,
     Info,
+    StatusBar,
     Completed,
+    Overdue,
+    SearchMatch,
+    Tag,
     Priority1,
     Priority2,
     Priority3,
@@ -350,6 +453,76 @@ impl<'de> Deserialize<'de> for Styles {
     }
 }
 
+/// A status a task can be in, the tasks in a `done` status are completed.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Status {
+    pub name: String,
+    /// the symbol shown in place of the checkbox.
+    pub glyph: String,
+    #[serde(default, deserialize_with = "deserialize_style")]
+    pub style: Style,
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// The statuses of the tasks in the order they are cycled through.
+#[derive(Clone, Debug, Default, Deref, DerefMut, Deserialize)]
+pub struct Statuses(pub Vec<Status>);
+
+impl Statuses {
+    /// the status of `task`, a task without one or with one missing from the configuration has
+    /// the first status of its completion.
+    pub fn of(&self, task: &Task) -> Option<&Status> {
+        task.status
+            .as_ref()
+            .and_then(|name| self.iter().find(|status| status.name == *name))
+            .filter(|status| status.done == task.completed)
+            .or_else(|| self.iter().find(|status| status.done == task.completed))
+    }
+
+    /// the position of the status of `task`, the completed tasks go last without statuses.
+    pub fn rank(&self, task: &Task) -> usize {
+        match self.of(task) {
+            Some(status) => self.iter().position(|s| s == status).unwrap_or_default(),
+            None => task.completed as usize,
+        }
+    }
+
+    /// the status after the one of `task`, or before it when going `backward`, going around.
+    pub fn cycle(&self, task: &Task, backward: bool) -> Option<&Status> {
+        let rank = self.rank(task);
+        let next = if backward {
+            rank + self.len() - 1
+        } else {
+            rank + 1
+        };
+        self.get(next.checked_rem(self.len())?)
+    }
+
+    /// checks the tasks can be both completed and not, and the names are unique.
+    fn validate(&self) -> Result<(), String> {
+        if !self.iter().any(|status| status.done) || self.iter().all(|status| status.done) {
+            return Err("the statuses need at least one done status and one that is not".into());
+        }
+        for (index, status) in self.iter().enumerate() {
+            if status.name.trim().is_empty() {
+                return Err("a status has no name".into());
+            }
+            if self[..index].iter().any(|s| s.name == status.name) {
+                return Err(format!("the status `{}` is defined twice", status.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn deserialize_style<'de, D>(deserializer: D) -> Result<Style, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(parse_style(&String::deserialize(deserializer)?))
+}
+
 pub fn parse_style(line: &str) -> Style {
     let (foreground, background) =
         line.split_at(line.to_lowercase().find("on ").unwrap_or(line.len()));
@@ -521,6 +694,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_statuses() {
+        let statuses = Config::fallback().statuses;
+        assert!(statuses.validate().is_ok());
+        let mut task = Task::default();
+        assert_eq!(statuses.of(&task).unwrap().name, "todo");
+        assert_eq!(statuses.cycle(&task, false).unwrap().name, "in-progress");
+        assert_eq!(statuses.cycle(&task, true).unwrap().name, "cancelled");
+
+        // a status that does not match the completion of the task is not kept.
+        task.status = Some("cancelled".into());
+        assert_eq!(statuses.of(&task).unwrap().name, "todo");
+        task.completed = true;
+        assert_eq!(statuses.of(&task).unwrap().name, "cancelled");
+        task.status = Some("removed".into());
+        assert_eq!(statuses.of(&task).unwrap().name, "done");
+        assert_eq!(statuses.rank(&task), 3);
+
+        let open_only = Statuses(vec![statuses[0].clone()]);
+        assert!(open_only.validate().is_err());
+        let mut twice = statuses.clone();
+        twice.push(statuses[3].clone());
+        assert!(twice.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_keybinding() {
+        let config = toml::from_str::<Config>("[keybindings.Navigation]\n\"<foo>\" = \"Quit\"");
+        assert!(config.is_err());
+    }
+
     #[test]
     fn test_simple_keys() {
         assert_eq!(
@@ -584,6 +788,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_key_sequence_to_string() {
+        for raw in ["<ctrl-shift-k>", "<g><g>", "<enter>", "<=>", "<f1>"] {
+            let keys = parse_key_sequence(raw).unwrap();
+            assert_eq!(key_sequence_to_string(&keys), raw);
+            assert_eq!(parse_key_sequence(raw), Ok(keys));
+        }
+    }
+
+    #[test]
+    fn test_replace_leader() {
+        let mut keybindings = KeyBindings::default();
+        keybindings.insert(
+            Mode::Navigation,
+            HashMap::from([(parse_key_sequence("<leader><f>").unwrap(), Action::Help)]),
+        );
+        assert!(keybindings.clone().replace_leader("<g><g>").is_err());
+        keybindings.replace_leader("<ctrl-x>").unwrap();
+        assert_eq!(
+            keybindings[&Mode::Navigation].get(&parse_key_sequence("<ctrl-x><f>").unwrap()),
+            Some(&Action::Help)
+        );
+    }
+
     #[test]
     fn test_invalid_keys() {
         assert!(parse_key_event("invalid-key").is_err());
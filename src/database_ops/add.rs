@@ -1,6 +1,7 @@
-use super::DatabaseOperations;
+use super::{is_unique_violation, DatabaseOperations};
 use crate::errors::DoMeError;
 use crate::structs::*;
+use chrono::Local;
 use color_eyre::{eyre::Ok, Result};
 use rusqlite::{params, Error};
 
@@ -12,39 +13,103 @@ impl DatabaseOperations {
             .conn
             .execute(ADD_WORKSPACE_QUERY, params![info.name, info.order])
         {
-            Err(Error::SqliteFailure(e, _)) => {
-                if e.code == rusqlite::ErrorCode::ConstraintViolation {
-                    Err(DoMeError::WorkspaceAlreadyExists(info.name).into())
-                } else {
-                    Err(e.into())
-                }
+            Err(Error::SqliteFailure(e, _)) if is_unique_violation(&e) => {
+                Err(DoMeError::WorkspaceAlreadyExists(info.name).into())
             }
             Err(e) => Err(e.into()),
             _ => Ok(()),
         }
     }
 
-    pub fn handle_add_task(&self, info: AddTask) -> Result<()> {
-        const ADD_TASK_QUERY: &str = "INSERT INTO Task (name, description, priority, task_order, workspaceid) VALUES (?, COALESCE(?, ''), COALESCE(?, 3), ?, ?)";
+    /// adds a task and returns its id, its history starts with its creation.
+    pub fn handle_add_task(&self, info: AddTask) -> Result<i32> {
+        const ADD_TASK_QUERY: &str = "INSERT INTO Task (name, description, priority, due_date, start_date, create_date, task_order, workspaceid, parentid, recurrence) VALUES (?, COALESCE(?, ''), COALESCE(?, 3), ?, ?, COALESCE(?, CURRENT_TIMESTAMP), ?, ?, ?, ?)";
         match self.conn.execute(
             ADD_TASK_QUERY,
             params![
                 info.name,
                 info.description,
                 info.priority,
+                info.due_date
+                    .map(|date| date.format(DATETIME_FORMAT).to_string()),
+                info.start_date
+                    .map(|date| date.format(DATETIME_FORMAT).to_string()),
+                info.create_date
+                    .map(|date| date.format(DATETIME_FORMAT).to_string()),
                 info.order,
-                info.workspace_id
+                info.workspace_id,
+                info.parent_id,
+                info.recurrence.as_ref().map(Recurrence::to_string)
             ],
         ) {
-            Err(Error::SqliteFailure(e, _)) => {
-                if e.code == rusqlite::ErrorCode::ConstraintViolation {
-                    Err(DoMeError::TaskAlreadyExists(info.name).into())
-                } else {
-                    Err(e.into())
-                }
+            Err(Error::SqliteFailure(e, _)) if is_unique_violation(&e) => {
+                Err(DoMeError::TaskAlreadyExists(info.name).into())
             }
             Err(e) => Err(e.into()),
-            _ => Ok(()),
+            _ => {
+                let id = self.conn.last_insert_rowid() as i32;
+                self.set_task_tags(id, &info.tags)?;
+                let task = self.get_task(id)?.expect("the task was just added");
+                // an imported task was created on the date it was exported with.
+                let created_at = info
+                    .create_date
+                    .unwrap_or_else(|| Local::now().naive_local());
+                self.add_task_event(&task, TaskEventKind::Created, None, None, created_at)?;
+                Ok(id)
+            }
         }
     }
+
+    /// inserts a task that was removed back with its id and at its order.
+    pub fn restore_task(&self, task: &Task) -> Result<()> {
+        const RESTORE_TASK_QUERY: &str = "INSERT INTO Task (id, name, description, priority, completed, create_date, due_date, start_date, task_order, workspaceid, parentid, recurrence, status, completed_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+        self.conn.execute(
+            RESTORE_TASK_QUERY,
+            params![
+                task.id,
+                task.name,
+                task.description,
+                task.priority,
+                task.completed,
+                task.create_date.format(DATETIME_FORMAT).to_string(),
+                task.due_date
+                    .map(|date| date.format(DATETIME_FORMAT).to_string()),
+                task.start_date
+                    .map(|date| date.format(DATETIME_FORMAT).to_string()),
+                task.order,
+                task.workspace_id,
+                task.parent_id,
+                task.recurrence.as_ref().map(Recurrence::to_string),
+                task.status,
+                task.completed_at
+                    .map(|date| date.format(DATETIME_FORMAT).to_string())
+            ],
+        )?;
+        self.set_task_tags(task.id, &task.tags)?;
+        Ok(())
+    }
+
+    /// inserts removed tasks back, the parents before their subtasks.
+    pub fn restore_tasks(&self, tasks: &[Task]) -> Result<()> {
+        for (_, task) in task_tree(tasks) {
+            self.restore_task(task)?;
+        }
+        Ok(())
+    }
+
+    /// inserts a workspace that was removed back with its id and at its order.
+    pub fn restore_workspace(&self, workspace: &Workspace) -> Result<()> {
+        const RESTORE_WORKSPACE_QUERY: &str = "INSERT INTO Workspace (id, name, workspace_order, create_date, update_date) VALUES (?, ?, ?, ?, ?)";
+        self.conn.execute(
+            RESTORE_WORKSPACE_QUERY,
+            params![
+                workspace.id,
+                workspace.name,
+                workspace.order,
+                workspace.create_date.format(DATETIME_FORMAT).to_string(),
+                workspace.update_date.format(DATETIME_FORMAT).to_string()
+            ],
+        )?;
+        Ok(())
+    }
 }
@@ -0,0 +1,134 @@
+use super::output::parse_datetime;
+use super::DatabaseOperations;
+use crate::structs::*;
+use chrono::NaiveDateTime;
+use color_eyre::{eyre::Ok, Result};
+use rusqlite::{params, types::Type, Error, Row};
+
+const TASK_EVENT_COLUMNS: &str = "SELECT TaskEvent.id, taskid, task_name, Workspace.name, kind, old_value, new_value, created_at FROM TaskEvent LEFT JOIN Workspace ON Workspace.id = TaskEvent.workspaceid";
+
+fn parse_task_event(row: &Row) -> rusqlite::Result<TaskEvent> {
+    let kind: String = row.get(4)?;
+    std::result::Result::Ok(TaskEvent {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        task_name: row.get(2)?,
+        workspace: row.get(3)?,
+        kind: kind
+            .parse()
+            .map_err(|e| Error::FromSqlConversionFailure(4, Type::Text, Box::new(e)))?,
+        old_value: row.get(5)?,
+        new_value: row.get(6)?,
+        created_at: parse_datetime(row, 7)?,
+    })
+}
+
+impl DatabaseOperations {
+    /// the history of a task, the last event first.
+    pub fn get_task_events(&self, task_id: i32) -> Result<Vec<TaskEvent>> {
+        let query = format!(
+            "{TASK_EVENT_COLUMNS} WHERE taskid = ? ORDER BY created_at DESC, TaskEvent.id DESC"
+        );
+        let mut stmt = self.conn.prepare(&query)?;
+        let events = stmt.query_map(params![task_id], parse_task_event)?;
+        Ok(events.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// what happened to every task since `since`, or ever, the last event first.
+    pub fn get_events_since(&self, since: Option<NaiveDateTime>) -> Result<Vec<TaskEvent>> {
+        let query = format!(
+            "{TASK_EVENT_COLUMNS} WHERE created_at >= COALESCE(?, '') ORDER BY created_at DESC, TaskEvent.id DESC"
+        );
+        let mut stmt = self.conn.prepare(&query)?;
+        let events = stmt.query_map(
+            params![since.map(|date| date.format(DATETIME_FORMAT).to_string())],
+            parse_task_event,
+        )?;
+        Ok(events.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// appends an event to the history of a task.
+    pub(super) fn add_task_event(
+        &self,
+        task: &Task,
+        kind: TaskEventKind,
+        old_value: Option<String>,
+        new_value: Option<String>,
+        now: NaiveDateTime,
+    ) -> Result<()> {
+        const ADD_TASK_EVENT_QUERY: &str = "INSERT INTO TaskEvent (taskid, workspaceid, task_name, kind, old_value, new_value, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)";
+        self.conn.execute(
+            ADD_TASK_EVENT_QUERY,
+            params![
+                task.id,
+                task.workspace_id,
+                task.name,
+                kind.to_string(),
+                old_value,
+                new_value,
+                now.format(DATETIME_FORMAT).to_string()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// writes in the history of a task what changed between `before` and `after` at `now`, a
+    /// completion is written at the date the task was completed, which an import keeps.
+    pub(super) fn add_task_changes(
+        &self,
+        before: &Task,
+        after: &Task,
+        now: NaiveDateTime,
+    ) -> Result<()> {
+        let mut events = Vec::new();
+        if before.name != after.name {
+            events.push((
+                TaskEventKind::Renamed,
+                Some(before.name.clone()),
+                Some(after.name.clone()),
+                now,
+            ));
+        }
+        if before.priority != after.priority {
+            events.push((
+                TaskEventKind::Reprioritized,
+                Some(priority_letter(before.priority).to_string()),
+                Some(priority_letter(after.priority).to_string()),
+                now,
+            ));
+        }
+        if before.parent_id != after.parent_id {
+            events.push((
+                TaskEventKind::Moved,
+                self.task_name(before.parent_id)?,
+                self.task_name(after.parent_id)?,
+                now,
+            ));
+        }
+        // a recurring task moves on to its next occurrence instead of staying completed, an undone
+        // completion takes its completion date back to the one before it.
+        match (before.completed_at, after.completed_at) {
+            (before_at, Some(completed_at)) if before_at < Some(completed_at) => {
+                events.push((TaskEventKind::Completed, None, None, completed_at));
+            }
+            (Some(_), after_at) if after_at != before.completed_at => {
+                events.push((TaskEventKind::Reopened, None, None, now));
+            }
+            _ if before.completed && !after.completed => {
+                events.push((TaskEventKind::Reopened, None, None, now));
+            }
+            _ => {}
+        }
+        for (kind, old_value, new_value, created_at) in events {
+            self.add_task_event(after, kind, old_value, new_value, created_at)?;
+        }
+        Ok(())
+    }
+
+    fn task_name(&self, id: Option<i32>) -> Result<Option<String>> {
+        Ok(match id {
+            Some(id) => self.get_task(id)?.map(|task| task.name),
+            None => None,
+        })
+    }
+}
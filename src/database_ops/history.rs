@@ -0,0 +1,252 @@
+use super::DatabaseOperations;
+use crate::action::Action;
+use crate::structs::*;
+use color_eyre::{eyre::Ok, Result};
+use std::collections::VecDeque;
+
+/// maximum number of changes that can be undone.
+const HISTORY_LIMIT: usize = 100;
+
+/// A change made to the database, holding what is needed to revert and reapply it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    AddTask(Task),
+    UpdateTask {
+        before: Task,
+        after: Task,
+    },
+    AddWorkspace(Workspace),
+    UpdateWorkspace {
+        before: Workspace,
+        after: Workspace,
+    },
+    /// a task or a workspace was removed and went to the trash.
+    Trash(TrashItem),
+    RestoreTrash(TrashItem),
+    PurgeTrash(Vec<TrashItem>),
+    /// changes made by a single action, undone and redone together.
+    Batch(Vec<Change>),
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    undo: VecDeque<Change>,
+    redo: Vec<Change>,
+}
+
+impl History {
+    fn push(&mut self, change: Change) {
+        if self.undo.len() == HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(change);
+        self.redo.clear();
+    }
+}
+
+impl DatabaseOperations {
+    /// Applies an update action and records its change so it can be undone.
+    pub(super) fn record_update_action(&mut self, action: Action) -> Result<()> {
+        // a change touching several rows is applied entirely or not at all.
+        let tx = self.conn.unchecked_transaction()?;
+        let change = match action {
+            Action::AddTask(info) => {
+                let id = self.handle_add_task(info)?;
+                Change::AddTask(self.get_task(id)?.expect("the task was just added"))
+            }
+            Action::UpdateTask(info) => {
+                let Some(change) = self.update_task_change(info)? else {
+                    return Ok(());
+                };
+                change
+            }
+            Action::UpdateTasks(infos) => {
+                let mut changes = Vec::with_capacity(infos.len());
+                for info in infos {
+                    match self.update_task_change(info)? {
+                        Some(Change::UpdateTask { before, after }) if before == after => {}
+                        Some(change) => changes.push(change),
+                        None => {}
+                    }
+                }
+                if changes.is_empty() {
+                    return Ok(());
+                }
+                Change::Batch(changes)
+            }
+            Action::RemoveTask(id) => {
+                let Some(item) = self.trash_task(id)? else {
+                    return Ok(());
+                };
+                Change::Trash(item)
+            }
+            Action::AddWorkspace(info) => {
+                self.handle_add_workspace(info)?;
+                let id = self.conn.last_insert_rowid() as i32;
+                Change::AddWorkspace(
+                    self.get_workspace(id)?
+                        .expect("the workspace was just added"),
+                )
+            }
+            Action::UpdateWorkspace(info) => {
+                let Some(before) = self.get_workspace(info.id)? else {
+                    return Ok(());
+                };
+                self.handle_update_workspace(info)?;
+                let after = self
+                    .get_workspace(before.id)?
+                    .expect("the workspace was updated");
+                Change::UpdateWorkspace { before, after }
+            }
+            Action::RemoveWorkspace(id) => {
+                let Some(item) = self.trash_workspace(id)? else {
+                    return Ok(());
+                };
+                Change::Trash(item)
+            }
+            Action::RestoreTrashItem(id) => {
+                let Some(item) = self.get_trash_item(id)? else {
+                    return Ok(());
+                };
+                self.restore_trash_item(&item)?;
+                Change::RestoreTrash(item)
+            }
+            Action::PurgeTrashItem(id) => {
+                let Some(item) = self.get_trash_item(id)? else {
+                    return Ok(());
+                };
+                self.purge_trash_items(std::slice::from_ref(&item))?;
+                Change::PurgeTrash(vec![item])
+            }
+            Action::EmptyTrash => {
+                let items = self.get_trash()?;
+                if items.is_empty() {
+                    return Ok(());
+                }
+                self.purge_trash_items(&items)?;
+                Change::PurgeTrash(items)
+            }
+            _ => return Ok(()),
+        };
+        tx.commit()?;
+        self.history.push(change);
+        Ok(())
+    }
+
+    /// Reverts the last recorded change, returns false when there is nothing to undo.
+    pub fn undo(&mut self) -> Result<bool> {
+        let Some(change) = self.history.undo.pop_back() else {
+            return Ok(false);
+        };
+        if let Err(e) = self.revert(&change) {
+            self.history.undo.push_back(change);
+            return Err(e);
+        }
+        self.history.redo.push(change);
+        Ok(true)
+    }
+
+    /// Reapplies the last undone change, returns false when there is nothing to redo.
+    pub fn redo(&mut self) -> Result<bool> {
+        let Some(change) = self.history.redo.pop() else {
+            return Ok(false);
+        };
+        if let Err(e) = self.reapply(&change) {
+            self.history.redo.push(change);
+            return Err(e);
+        }
+        self.history.undo.push_back(change);
+        Ok(true)
+    }
+
+    fn revert(&self, change: &Change) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.revert_change(change)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn revert_change(&self, change: &Change) -> Result<()> {
+        match change {
+            Change::AddTask(task) => self.handle_remove_task(task.id)?,
+            Change::UpdateTask { before, .. } => self.set_task(before)?,
+            Change::AddWorkspace(workspace) => self.handle_remove_workspace(workspace.id)?,
+            Change::UpdateWorkspace { before, .. } => self.set_workspace(before)?,
+            Change::Trash(item) => self.restore_trash_item(item)?,
+            Change::RestoreTrash(item) => self.put_back_in_trash(item)?,
+            Change::PurgeTrash(items) => {
+                for item in items {
+                    self.insert_trash_item(item)?;
+                }
+            }
+            // the changes depend on the ones before them, they are reverted from the last.
+            Change::Batch(changes) => {
+                for change in changes.iter().rev() {
+                    self.revert_change(change)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn reapply(&self, change: &Change) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.reapply_change(change)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn reapply_change(&self, change: &Change) -> Result<()> {
+        match change {
+            Change::AddTask(task) => self.restore_task(task)?,
+            Change::UpdateTask { after, .. } => self.set_task(after)?,
+            Change::AddWorkspace(workspace) => self.restore_workspace(workspace)?,
+            Change::UpdateWorkspace { after, .. } => self.set_workspace(after)?,
+            Change::Trash(item) => self.put_back_in_trash(item)?,
+            Change::RestoreTrash(item) => self.restore_trash_item(item)?,
+            Change::PurgeTrash(items) => self.purge_trash_items(items)?,
+            Change::Batch(changes) => {
+                for change in changes {
+                    self.reapply_change(change)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// updates a task and returns the change, none when the task does not exist.
+    fn update_task_change(&self, info: UpdateTask) -> Result<Option<Change>> {
+        let Some(before) = self.get_task(info.id)? else {
+            return Ok(None);
+        };
+        self.handle_update_task(info)?;
+        let after = self.get_task(before.id)?.expect("the task was updated");
+        Ok(Some(Change::UpdateTask { before, after }))
+    }
+
+    fn set_task(&self, task: &Task) -> Result<()> {
+        self.handle_update_task(UpdateTask {
+            id: task.id,
+            name: Some(task.name.clone()),
+            description: Some(task.description.clone()),
+            priority: Some(task.priority),
+            completed: Some(task.completed),
+            due_date: Some(task.due_date),
+            start_date: Some(task.start_date),
+            order: Some(task.order),
+            tags: Some(task.tags.clone()),
+            parent_id: Some(task.parent_id),
+            recurrence: Some(task.recurrence.clone()),
+            status: Some(task.status.clone()),
+            completed_at: Some(task.completed_at),
+        })
+    }
+
+    fn set_workspace(&self, workspace: &Workspace) -> Result<()> {
+        self.handle_update_workspace(UpdateWorkspace {
+            id: workspace.id,
+            name: Some(workspace.name.clone()),
+            order: Some(workspace.order),
+        })
+    }
+}
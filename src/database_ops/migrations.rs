@@ -0,0 +1,70 @@
+use crate::errors::DoMeError;
+use color_eyre::Result;
+use rusqlite::Connection;
+use std::path::Path;
+use tracing::info;
+
+/// Ordered migration steps, the step at index `i` upgrades the database to version `i + 1`.
+/// Never edit a released step, add a new one instead.
+pub const MIGRATIONS: &[&str] = &[
+    include_str!("../../sql/migrations/001_initial.sql"),
+    include_str!("../../sql/migrations/002_task_dates.sql"),
+    include_str!("../../sql/migrations/003_trigger_control_cleanup.sql"),
+    include_str!("../../sql/migrations/004_task_search.sql"),
+    include_str!("../../sql/migrations/005_tags.sql"),
+    include_str!("../../sql/migrations/006_subtasks.sql"),
+    include_str!("../../sql/migrations/007_recurrence.sql"),
+    include_str!("../../sql/migrations/008_trash.sql"),
+    include_str!("../../sql/migrations/009_status.sql"),
+    include_str!("../../sql/migrations/010_task_events.sql"),
+];
+
+pub const LATEST_VERSION: i32 = MIGRATIONS.len() as i32;
+
+pub fn get_version(conn: &Connection) -> Result<i32> {
+    let version: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version != 0 {
+        return Ok(version);
+    }
+    // databases created before versioning was introduced have the initial schema but no version.
+    let has_tables: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'Workspace')",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(if has_tables { 1 } else { 0 })
+}
+
+/// Brings the database up to `LATEST_VERSION`, each step runs in its own transaction.
+/// When `backup_path` is given and the database holds data, a copy is written there first.
+pub fn migrate(conn: &mut Connection, backup_path: Option<&Path>) -> Result<()> {
+    let version = get_version(conn)?;
+    if version > LATEST_VERSION {
+        return Err(DoMeError::UnsupportedDatabaseVersion(version).into());
+    }
+    if version == LATEST_VERSION {
+        return Ok(());
+    }
+
+    if let (Some(backup_path), true) = (backup_path, version > 0) {
+        info!(
+            "Backing up the database version {} to {}",
+            version,
+            backup_path.display()
+        );
+        if backup_path.exists() {
+            std::fs::remove_file(backup_path)?;
+        }
+        conn.execute("VACUUM INTO ?", [backup_path.to_string_lossy()])?;
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        let target_version = index as i32 + 1;
+        info!("Migrating the database to version {}", target_version);
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", target_version)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
@@ -1,37 +1,57 @@
 use std::path::PathBuf;
 
 use crate::action::Action;
-use color_eyre::eyre::Ok;
 use color_eyre::Result;
+use history::History;
 use rusqlite::Connection;
 mod add;
+mod events;
+pub mod history;
+mod migrations;
 mod output;
 mod remove;
+pub(crate) mod search;
 #[cfg(test)]
 mod tests;
+pub mod transfer;
+mod trash;
 mod update;
 
+/// whether a statement failed on a UNIQUE constraint, the names of the workspaces and of the tasks
+/// of a workspace are the only ones.
+fn is_unique_violation(error: &rusqlite::ffi::Error) -> bool {
+    error.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE
+}
+
+/// name of the database file inside the data directory.
+pub const DATABASE_FILE: &str = "do_me.sqlite";
+
 pub struct DatabaseOperations {
     conn: Connection,
+    history: History,
 }
 
 impl DatabaseOperations {
     pub fn new(database_path: PathBuf) -> DatabaseOperations {
-        let conn = Connection::open(database_path).unwrap();
-        conn.execute_batch(include_str!("../../sql/schema.sql"))
+        let mut conn = Connection::open(&database_path).unwrap();
+        conn.execute_batch(include_str!("../../sql/pragmas.sql"))
             .expect("Error setting up the database");
-        DatabaseOperations { conn }
+        // in-memory databases have nothing worth backing up.
+        let backup_path = (database_path != *":memory:").then(|| {
+            let version =
+                migrations::get_version(&conn).expect("Error reading the database version");
+            PathBuf::from(format!("{}.v{}.bak", database_path.display(), version))
+        });
+        migrations::migrate(&mut conn, backup_path.as_deref())
+            .expect("Error migrating the database");
+        DatabaseOperations {
+            conn,
+            history: History::default(),
+        }
     }
 
-    pub fn handle_update_actions(&self, action: Action) -> Result<()> {
-        match action {
-            Action::AddWorkspace(info) => self.handle_add_workspace(info),
-            Action::AddTask(info) => self.handle_add_task(info),
-            Action::UpdateWorkspace(info) => self.handle_update_workspace(info),
-            Action::UpdateTask(info) => self.handle_update_task(info),
-            Action::RemoveWorkspace(id) => self.handle_remove_workspace(id),
-            Action::RemoveTask(id) => self.handle_remove_task(id),
-            _ => Ok(()),
-        }
+    /// handles the actions coming from the interface, they are recorded so they can be undone.
+    pub fn handle_update_actions(&mut self, action: Action) -> Result<()> {
+        self.record_update_action(action)
     }
 }
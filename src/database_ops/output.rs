@@ -2,56 +2,90 @@ use super::DatabaseOperations;
 use crate::structs::*;
 use chrono::NaiveDateTime;
 use color_eyre::Result;
-use rusqlite::{params, Error, Row};
+use rusqlite::{params, types::Type, Error, Row};
 
 pub fn parse_datetime(row: &Row, index: usize) -> rusqlite::Result<NaiveDateTime> {
     let date_str: String = row.get(index)?;
-    Ok(NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").unwrap())
+    NaiveDateTime::parse_from_str(&date_str, DATETIME_FORMAT)
+        .map_err(|e| Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+pub fn parse_optional_datetime(row: &Row, index: usize) -> rusqlite::Result<Option<NaiveDateTime>> {
+    let date_str: Option<String> = row.get(index)?;
+    date_str
+        .map(|date_str| {
+            NaiveDateTime::parse_from_str(&date_str, DATETIME_FORMAT)
+                .map_err(|e| Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+        })
+        .transpose()
+}
+
+/// reads the recurrence of a task, a rule that can not be parsed anymore is ignored.
+pub fn parse_recurrence(row: &Row, index: usize) -> rusqlite::Result<Option<Recurrence>> {
+    let recurrence: Option<String> = row.get(index)?;
+    Ok(recurrence.and_then(|recurrence| recurrence.parse().ok()))
+}
+
+/// reads the tags of a task selected as a space separated list, tags never contain spaces.
+pub fn parse_tags(row: &Row, index: usize) -> rusqlite::Result<Vec<String>> {
+    let tags: Option<String> = row.get(index)?;
+    Ok(normalize_tags(
+        tags.iter()
+            .flat_map(|tags| tags.split(' '))
+            .map(str::to_string),
+    ))
 }
 
 impl DatabaseOperations {
-    // pub fn get_task(&self, id: i32) -> Result<Option<Task>> {
-    //     const GET_TASK_QUERY: &str = "SELECT name, task_order, description, priority, completed, create_date, workspaceid FROM Task WHERE id = ?";
-    //     match self.conn.query_row(GET_TASK_QUERY, params![id], |row| {
-    //         Ok(Task {
-    //             id,
-    //             name: row.get(0)?,
-    //             order: row.get(1)?,
-    //             description: row.get(2)?,
-    //             priority: row.get(3)?,
-    //             completed: row.get(4)?,
-    //             create_date: parse_datetime(row, 5)?,
-    //             workspace_id: row.get(6)?,
-    //         })
-    //     }) {
-    //         Ok(task) => Ok(Some(task)),
-    //         Err(Error::QueryReturnedNoRows) => Ok(None),
-    //         Err(e) => Err(e.into()),
-    //     }
-    // }
-    //
-    // pub fn get_workspace(&self, id: i32) -> Result<Option<Workspace>> {
-    //     const GET_WORKSPACE_QUERY: &str =
-    //         "SELECT name, workspace_order, create_date, update_date FROM Workspace WHERE id = ?";
-    //     match self
-    //         .conn
-    //         .query_row(GET_WORKSPACE_QUERY, params![id], |row| {
-    //             Ok(Workspace {
-    //                 id,
-    //                 name: row.get(0)?,
-    //                 order: row.get(1)?,
-    //                 create_date: parse_datetime(row, 2)?,
-    //                 update_date: parse_datetime(row, 3)?,
-    //             })
-    //         }) {
-    //         Ok(workspace) => Ok(Some(workspace)),
-    //         Err(Error::QueryReturnedNoRows) => Ok(None),
-    //         Err(e) => Err(e.into()),
-    //     }
-    // }
-    //
+    pub fn get_task(&self, id: i32) -> Result<Option<Task>> {
+        const GET_TASK_QUERY: &str = "SELECT name, task_order, description, priority, completed, create_date, due_date, start_date, workspaceid, (SELECT group_concat(Tag.name, ' ') FROM TaskTag JOIN Tag ON Tag.id = TaskTag.tagid WHERE TaskTag.taskid = Task.id), parentid, recurrence, status, completed_at FROM Task WHERE id = ?";
+        match self.conn.query_row(GET_TASK_QUERY, params![id], |row| {
+            Ok(Task {
+                id,
+                name: row.get(0)?,
+                order: row.get(1)?,
+                description: row.get(2)?,
+                priority: row.get(3)?,
+                completed: row.get(4)?,
+                create_date: parse_datetime(row, 5)?,
+                due_date: parse_optional_datetime(row, 6)?,
+                start_date: parse_optional_datetime(row, 7)?,
+                workspace_id: row.get(8)?,
+                tags: parse_tags(row, 9)?,
+                parent_id: row.get(10)?,
+                recurrence: parse_recurrence(row, 11)?,
+                status: row.get(12)?,
+                completed_at: parse_optional_datetime(row, 13)?,
+            })
+        }) {
+            Ok(task) => Ok(Some(task)),
+            Err(Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get_workspace(&self, id: i32) -> Result<Option<Workspace>> {
+        const GET_WORKSPACE_QUERY: &str =
+            "SELECT name, workspace_order, create_date, update_date FROM Workspace WHERE id = ?";
+        match self
+            .conn
+            .query_row(GET_WORKSPACE_QUERY, params![id], |row| {
+                Ok(Workspace {
+                    id,
+                    name: row.get(0)?,
+                    order: row.get(1)?,
+                    create_date: parse_datetime(row, 2)?,
+                    update_date: parse_datetime(row, 3)?,
+                })
+            }) {
+            Ok(workspace) => Ok(Some(workspace)),
+            Err(Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     pub fn get_tasks(&self, workspace_id: i32) -> Result<Vec<Task>> {
-        const GET_TASKS_QUERY: &str = "SELECT id, name, task_order, description, priority, completed, create_date FROM Task WHERE workspaceid = ?;";
+        const GET_TASKS_QUERY: &str = "SELECT id, name, task_order, description, priority, completed, create_date, due_date, start_date, (SELECT group_concat(Tag.name, ' ') FROM TaskTag JOIN Tag ON Tag.id = TaskTag.tagid WHERE TaskTag.taskid = Task.id), parentid, recurrence, status, completed_at FROM Task WHERE workspaceid = ?;";
 
         let mut stmt = self.conn.prepare(GET_TASKS_QUERY)?;
         let task_iter = stmt.query_map(params![workspace_id], |row| {
@@ -63,10 +97,31 @@ impl DatabaseOperations {
                 priority: row.get(4)?,
                 completed: row.get(5)?,
                 create_date: parse_datetime(row, 6)?,
+                due_date: parse_optional_datetime(row, 7)?,
+                start_date: parse_optional_datetime(row, 8)?,
                 workspace_id,
+                tags: parse_tags(row, 9)?,
+                parent_id: row.get(10)?,
+                recurrence: parse_recurrence(row, 11)?,
+                status: row.get(12)?,
+                completed_at: parse_optional_datetime(row, 13)?,
             })
         })?;
-        Ok(task_iter.map(|task| task.unwrap()).collect())
+        Ok(task_iter.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// the subtasks of a task and their own subtasks, at any depth.
+    pub fn get_subtasks(&self, id: i32) -> Result<Vec<Task>> {
+        const GET_SUBTASKS_QUERY: &str = "WITH RECURSIVE Subtask(id) AS (SELECT id FROM Task WHERE parentid = ? UNION ALL SELECT Task.id FROM Task JOIN Subtask ON Task.parentid = Subtask.id) SELECT id FROM Subtask";
+        let mut stmt = self.conn.prepare(GET_SUBTASKS_QUERY)?;
+        let ids = stmt
+            .query_map(params![id], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<i32>>>()?;
+        let mut subtasks = Vec::with_capacity(ids.len());
+        for id in ids {
+            subtasks.extend(self.get_task(id)?);
+        }
+        Ok(subtasks)
     }
 
     pub fn get_workspaces(&self) -> Result<Vec<Workspace>> {
@@ -82,7 +137,7 @@ impl DatabaseOperations {
                 update_date: parse_datetime(row, 4)?,
             })
         })?;
-        Ok(workspace_iter.map(|workspace| workspace.unwrap()).collect())
+        Ok(workspace_iter.collect::<rusqlite::Result<_>>()?)
     }
 
     pub fn search_task_name(&self, name: &str, workspace_id: i32) -> Result<Option<i32>> {
@@ -0,0 +1,71 @@
+use super::{output::*, DatabaseOperations};
+use crate::structs::*;
+use color_eyre::Result;
+use rusqlite::params;
+
+/// matches in the name weigh more than matches in the description.
+const NAME_WEIGHT: f64 = 10.0;
+const DESCRIPTION_WEIGHT: f64 = 1.0;
+/// number of words of the description kept in the snippet, around the match when there is one.
+const SNIPPET_WORDS: i32 = 8;
+
+/// Turns what the user typed into an FTS5 query, every word must appear in the task and the
+/// last one may be incomplete. Quoting the words keeps the FTS5 syntax from leaking to the user.
+pub fn fts_query(input: &str) -> String {
+    let words: Vec<String> = input
+        .split_whitespace()
+        // words without letters or digits are not indexed, they would match nothing.
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+    match words.len() {
+        0 => String::new(),
+        _ => words.join(" ") + "*",
+    }
+}
+
+impl DatabaseOperations {
+    /// searches the names and descriptions of the tasks of every workspace, best matches first.
+    pub fn search_tasks(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        const SEARCH_TASKS_QUERY: &str = "SELECT Task.id, Task.name, Task.task_order, Task.description, Task.priority, Task.completed, Task.create_date, Task.due_date, Task.start_date, Task.workspaceid, (SELECT group_concat(Tag.name, ' ') FROM TaskTag JOIN Tag ON Tag.id = TaskTag.tagid WHERE TaskTag.taskid = Task.id), Task.parentid, Task.recurrence, Task.status, Task.completed_at, Workspace.name, snippet(TaskSearch, 1, '', '', '…', ?) FROM TaskSearch JOIN Task ON Task.id = TaskSearch.rowid JOIN Workspace ON Workspace.id = Task.workspaceid WHERE TaskSearch MATCH ? ORDER BY bm25(TaskSearch, ?, ?) LIMIT ?";
+
+        let query = fts_query(query);
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut stmt = self.conn.prepare(SEARCH_TASKS_QUERY)?;
+        let result_iter = stmt.query_map(
+            params![
+                SNIPPET_WORDS,
+                query,
+                NAME_WEIGHT,
+                DESCRIPTION_WEIGHT,
+                limit as i64
+            ],
+            |row| {
+                Ok(SearchResult {
+                    task: Task {
+                        id: row.get(0)?,
+                        name: row.get(1)?,
+                        order: row.get(2)?,
+                        description: row.get(3)?,
+                        priority: row.get(4)?,
+                        completed: row.get(5)?,
+                        create_date: parse_datetime(row, 6)?,
+                        due_date: parse_optional_datetime(row, 7)?,
+                        start_date: parse_optional_datetime(row, 8)?,
+                        workspace_id: row.get(9)?,
+                        tags: parse_tags(row, 10)?,
+                        parent_id: row.get(11)?,
+                        recurrence: parse_recurrence(row, 12)?,
+                        status: row.get(13)?,
+                        completed_at: parse_optional_datetime(row, 14)?,
+                    },
+                    workspace: row.get(15)?,
+                    snippet: row.get(16)?,
+                })
+            },
+        )?;
+        Ok(result_iter.collect::<rusqlite::Result<_>>()?)
+    }
+}
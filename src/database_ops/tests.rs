@@ -1,3 +1,18 @@
+use crate::database_ops::DatabaseOperations;
+use crate::structs::AddWorkspace;
+
+/// an empty database with a single workspace, along with the id of the workspace.
+fn database_with_workspace(name: &str) -> (DatabaseOperations, i32) {
+    let db = DatabaseOperations::new(":memory:".into());
+    db.handle_add_workspace(AddWorkspace {
+        name: name.into(),
+        ..Default::default()
+    })
+    .unwrap();
+    let workspace_id = db.search_workspace_name(name).unwrap().unwrap();
+    (db, workspace_id)
+}
+
 mod test_database_opearations {
 
     use crate::database_ops::DatabaseOperations;
@@ -320,4 +335,1521 @@ mod test_database_opearations {
             }
         }
     }
+
+    #[test]
+    fn test_task_dates() {
+        let db = DatabaseOperations::new(":memory:".into());
+        db.handle_add_workspace(AddWorkspace {
+            name: "the workspace".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        let workspace_id = db.search_workspace_name("the workspace").unwrap().unwrap();
+
+        let due_date = parse_date_input("2024-05-01 14:30").unwrap();
+        let start_date = parse_date_input("2024-04-20").unwrap();
+        db.handle_add_task(AddTask {
+            name: "dated".into(),
+            due_date: Some(due_date),
+            start_date: Some(start_date),
+            workspace_id,
+            ..Default::default()
+        })
+        .unwrap();
+        db.handle_add_task(AddTask {
+            name: "undated".into(),
+            workspace_id,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let tasks = db.get_tasks(workspace_id).unwrap();
+        assert_eq!(tasks[0].due_date, Some(due_date));
+        assert_eq!(tasks[0].start_date, Some(start_date));
+        assert_eq!(tasks[1].due_date, None);
+        assert_eq!(tasks[1].start_date, None);
+
+        // updating other fields keeps the dates.
+        let id = db.search_task_name("dated", workspace_id).unwrap().unwrap();
+        db.handle_update_task(UpdateTask {
+            id,
+            priority: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+        let tasks = db.get_tasks(workspace_id).unwrap();
+        assert_eq!(tasks[0].due_date, Some(due_date));
+
+        // clearing the due date.
+        db.handle_update_task(UpdateTask {
+            id,
+            due_date: Some(None),
+            ..Default::default()
+        })
+        .unwrap();
+        let tasks = db.get_tasks(workspace_id).unwrap();
+        assert_eq!(tasks[0].due_date, None);
+        assert_eq!(tasks[0].start_date, Some(start_date));
+    }
+
+    #[test]
+    fn test_sort_by_due_date() {
+        let mut tasks: Vec<Task> = ["2024-05-03", "", "2024-05-01", "2024-05-02"]
+            .iter()
+            .enumerate()
+            .map(|(order, date)| Task {
+                name: date.to_string(),
+                due_date: parse_date_input(date),
+                order,
+                ..Default::default()
+            })
+            .collect();
+        TaskSorter::new(TaskSortType::DueDate, false).sort(&mut tasks);
+        let names: Vec<&str> = tasks.iter().map(|task| task.name.as_str()).collect();
+        assert_eq!(names, vec!["2024-05-01", "2024-05-02", "2024-05-03", ""]);
+    }
+
+    #[test]
+    fn test_invalid_date_is_an_error() {
+        let db = DatabaseOperations::new(":memory:".into());
+        db.handle_add_workspace(AddWorkspace {
+            name: "the workspace".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        let workspace_id = db.search_workspace_name("the workspace").unwrap().unwrap();
+        db.handle_add_task(AddTask {
+            name: "the task".into(),
+            workspace_id,
+            ..Default::default()
+        })
+        .unwrap();
+        db.conn
+            .execute("UPDATE Task SET create_date = 'not a date'", [])
+            .unwrap();
+        assert!(db.get_tasks(workspace_id).is_err());
+    }
+}
+
+mod test_migrations {
+
+    use crate::database_ops::migrations::{get_version, migrate, LATEST_VERSION, MIGRATIONS};
+    use crate::database_ops::DatabaseOperations;
+    use pretty_assertions::assert_eq;
+    use rusqlite::Connection;
+    use std::path::PathBuf;
+
+    /// creates a database the way do-me did before versioning was introduced.
+    fn create_v1_database(conn: &Connection) {
+        conn.execute_batch(include_str!("../../sql/pragmas.sql"))
+            .unwrap();
+        conn.execute_batch(MIGRATIONS[0]).unwrap();
+        conn.execute_batch(
+            "INSERT INTO Workspace (name) VALUES ('work');
+             INSERT INTO Task (name, priority, workspaceid) VALUES ('first', 1, 1);
+             INSERT INTO Task (name, workspaceid) VALUES ('second', 1);
+             INSERT INTO trigger_control VALUES (0);",
+        )
+        .unwrap();
+    }
+
+    fn temp_database_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("do-me-test-{}-{}", std::process::id(), name));
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("do_me.sqlite")
+    }
+
+    #[test]
+    fn test_new_database_is_latest() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        assert_eq!(get_version(&conn).unwrap(), 0);
+        migrate(&mut conn, None).unwrap();
+        assert_eq!(get_version(&conn).unwrap(), LATEST_VERSION);
+
+        // migrating an up to date database is a no-op.
+        migrate(&mut conn, None).unwrap();
+        assert_eq!(get_version(&conn).unwrap(), LATEST_VERSION);
+    }
+
+    #[test]
+    fn test_upgrade_v1_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        create_v1_database(&conn);
+        assert_eq!(get_version(&conn).unwrap(), 1);
+
+        migrate(&mut conn, None).unwrap();
+        assert_eq!(get_version(&conn).unwrap(), LATEST_VERSION);
+
+        let rows: i32 = conn
+            .query_row("SELECT COUNT(*) FROM trigger_control", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 1);
+
+        let db = DatabaseOperations {
+            conn,
+            history: Default::default(),
+        };
+        let tasks = db.get_tasks(1).unwrap();
+        assert_eq!(
+            tasks
+                .iter()
+                .map(|task| (task.order, task.name.as_str(), task.priority, task.due_date))
+                .collect::<Vec<_>>(),
+            vec![(0, "first", 1, None), (1, "second", 3, None)]
+        );
+        // the tasks created before the search index are indexed by the upgrade.
+        assert_eq!(db.search_tasks("seco", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_upgrade_backs_up_database() {
+        let path = temp_database_path("backup");
+        create_v1_database(&Connection::open(&path).unwrap());
+
+        let db = DatabaseOperations::new(path.clone());
+        assert_eq!(db.get_workspaces().unwrap().len(), 1);
+
+        let backup_path = PathBuf::from(format!("{}.v1.bak", path.display()));
+        let backup = Connection::open(&backup_path).unwrap();
+        assert_eq!(get_version(&backup).unwrap(), 1);
+        let tasks: i32 = backup
+            .query_row("SELECT COUNT(*) FROM Task", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(tasks, 2);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_failed_migration_is_rolled_back() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        create_v1_database(&conn);
+        // a column that the next migration is going to add makes it fail halfway.
+        conn.execute_batch("ALTER TABLE Task ADD COLUMN start_date datetime;")
+            .unwrap();
+
+        assert!(migrate(&mut conn, None).is_err());
+        assert_eq!(get_version(&conn).unwrap(), 1);
+        let has_due_date: bool = conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM pragma_table_info('Task') WHERE name = 'due_date')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!has_due_date);
+    }
+
+    #[test]
+    fn test_newer_database_is_rejected() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", LATEST_VERSION + 1)
+            .unwrap();
+        assert!(migrate(&mut conn, None).is_err());
+    }
+}
+
+mod test_transfer {
+
+    use crate::database_ops::transfer::{ConflictPolicy, ImportMode, ImportSummary};
+    use crate::database_ops::DatabaseOperations;
+    use crate::structs::*;
+    use pretty_assertions::assert_eq;
+
+    fn setup() -> DatabaseOperations {
+        let db = DatabaseOperations::new(":memory:".into());
+        for workspace in ["work", "home"] {
+            db.handle_add_workspace(AddWorkspace {
+                name: workspace.into(),
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        let work = db.search_workspace_name("work").unwrap().unwrap();
+        let home = db.search_workspace_name("home").unwrap().unwrap();
+        for (name, workspace_id) in [("report", work), ("review", work), ("dishes", home)] {
+            db.handle_add_task(AddTask {
+                name: name.into(),
+                description: Some(format!("{name} description")),
+                priority: Some(2),
+                due_date: parse_date_input("2024-05-01 10:00"),
+                workspace_id,
+                tags: vec![format!("{name}-tag")],
+                recurrence: (name == "dishes").then_some(Recurrence::Daily),
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        let id = db.search_task_name("review", work).unwrap().unwrap();
+        db.handle_update_task(UpdateTask {
+            id,
+            completed: Some(true),
+            ..Default::default()
+        })
+        .unwrap();
+        db
+    }
+
+    /// the exported documents without their export date, used to compare databases.
+    fn contents(db: &DatabaseOperations) -> Vec<ExportWorkspace> {
+        db.export().unwrap().workspaces
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let source = setup();
+        let document = source.export().unwrap();
+        let json = serde_json::to_string(&document).unwrap();
+
+        let target = DatabaseOperations::new(":memory:".into());
+        let summary = target
+            .import(
+                serde_json::from_str(&json).unwrap(),
+                ImportMode::Merge,
+                ConflictPolicy::Fail,
+            )
+            .unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                workspaces: 2,
+                tasks: 3,
+                ..Default::default()
+            }
+        );
+        assert_eq!(contents(&target), contents(&source));
+    }
+
+    #[test]
+    fn test_import_merge_conflicts() {
+        let db = setup();
+        let mut document = db.export().unwrap();
+        document.workspaces[0].tasks[0].description = "changed".into();
+        document.workspaces[0].tasks.push(ExportTask {
+            name: "new task".into(),
+            priority: 1,
+            ..Default::default()
+        });
+
+        // skipping keeps the existing task.
+        let summary = db
+            .import(document.clone(), ImportMode::Merge, ConflictPolicy::Skip)
+            .unwrap();
+        assert_eq!(summary.tasks, 1);
+        assert_eq!(summary.skipped, 3);
+        let work = &contents(&db)[0];
+        assert_eq!(work.tasks[0].description, "report description");
+        assert_eq!(work.tasks[2].name, "new task");
+
+        // failing leaves the database untouched.
+        let before = contents(&db);
+        assert!(db
+            .import(document.clone(), ImportMode::Merge, ConflictPolicy::Fail)
+            .is_err());
+        assert_eq!(contents(&db), before);
+
+        // overwriting updates the existing task in place.
+        let summary = db
+            .import(document, ImportMode::Merge, ConflictPolicy::Overwrite)
+            .unwrap();
+        assert_eq!(summary.overwritten, 4);
+        let work = &contents(&db)[0];
+        assert_eq!(work.tasks[0].name, "report");
+        assert_eq!(work.tasks[0].description, "changed");
+    }
+
+    #[test]
+    fn test_import_replace() {
+        let db = setup();
+        let document = ExportDocument {
+            version: 1,
+            workspaces: vec![ExportWorkspace {
+                name: "fresh".into(),
+                tasks: vec![ExportTask {
+                    name: "only task".into(),
+                    priority: 3,
+                    ..Default::default()
+                }],
+            }],
+            ..Default::default()
+        };
+        db.import(document, ImportMode::Replace, ConflictPolicy::Fail)
+            .unwrap();
+        let workspaces = contents(&db);
+        assert_eq!(workspaces.len(), 1);
+        assert_eq!(workspaces[0].name, "fresh");
+        assert_eq!(workspaces[0].tasks[0].name, "only task");
+    }
+
+    #[test]
+    fn test_import_newer_version() {
+        let db = setup();
+        let document = ExportDocument {
+            version: 99,
+            ..Default::default()
+        };
+        assert!(db
+            .import(document, ImportMode::Replace, ConflictPolicy::Fail)
+            .is_err());
+        assert_eq!(contents(&db).len(), 2);
+    }
+}
+
+mod test_history {
+
+    use crate::action::Action;
+    use crate::database_ops::DatabaseOperations;
+    use crate::structs::*;
+    use pretty_assertions::assert_eq;
+
+    fn snapshot(db: &DatabaseOperations) -> Vec<(Workspace, Vec<Task>)> {
+        let mut workspaces = db.get_workspaces().unwrap();
+        WorkspaceSorter::default().sort(&mut workspaces);
+        workspaces
+            .into_iter()
+            .map(|workspace| {
+                let mut tasks = db.get_tasks(workspace.id).unwrap();
+                TaskSorter::default().sort(&mut tasks);
+                // the update date is touched by the triggers when tasks are restored.
+                let workspace = Workspace {
+                    update_date: Default::default(),
+                    ..workspace
+                };
+                (workspace, tasks)
+            })
+            .collect()
+    }
+
+    fn setup() -> DatabaseOperations {
+        let mut db = DatabaseOperations::new(":memory:".into());
+        for name in ["first", "second", "third"] {
+            db.handle_update_actions(Action::AddWorkspace(AddWorkspace {
+                name: name.into(),
+                ..Default::default()
+            }))
+            .unwrap();
+        }
+        let workspace_id = db.search_workspace_name("second").unwrap().unwrap();
+        for name in ["a", "b", "c", "d"] {
+            db.handle_update_actions(Action::AddTask(AddTask {
+                name: name.into(),
+                priority: Some(2),
+                workspace_id,
+                ..Default::default()
+            }))
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_undo_redo_every_action() {
+        let mut db = setup();
+        let workspace_id = db.search_workspace_name("second").unwrap().unwrap();
+        let task_id = db.search_task_name("b", workspace_id).unwrap().unwrap();
+        let actions = vec![
+            Action::UpdateTask(UpdateTask {
+                id: task_id,
+                name: Some("renamed".into()),
+                completed: Some(true),
+                due_date: Some(parse_date_input("2024-05-01")),
+                order: Some(3),
+                ..Default::default()
+            }),
+            Action::RemoveTask(db.search_task_name("c", workspace_id).unwrap().unwrap()),
+            Action::AddTask(AddTask {
+                name: "inserted".into(),
+                order: Some(1),
+                workspace_id,
+                ..Default::default()
+            }),
+            Action::UpdateWorkspace(UpdateWorkspace {
+                id: workspace_id,
+                name: Some("renamed workspace".into()),
+                order: Some(0),
+            }),
+            Action::AddWorkspace(AddWorkspace {
+                name: "fourth".into(),
+                order: Some(1),
+            }),
+            Action::RemoveWorkspace(workspace_id),
+        ];
+
+        let mut snapshots = vec![snapshot(&db)];
+        for action in actions {
+            db.handle_update_actions(action).unwrap();
+            snapshots.push(snapshot(&db));
+        }
+
+        for expected in snapshots.iter().rev().skip(1) {
+            assert!(db.undo().unwrap());
+            assert_eq!(&snapshot(&db), expected);
+        }
+
+        for expected in snapshots.iter().skip(1) {
+            assert!(db.redo().unwrap());
+            assert_eq!(&snapshot(&db), expected);
+        }
+        assert!(!db.redo().unwrap());
+    }
+
+    #[test]
+    fn test_undo_removed_workspace_restores_tasks() {
+        let mut db = setup();
+        let before = snapshot(&db);
+        let workspace_id = db.search_workspace_name("second").unwrap().unwrap();
+        db.handle_update_actions(Action::RemoveWorkspace(workspace_id))
+            .unwrap();
+        assert!(db.get_tasks(workspace_id).unwrap().is_empty());
+
+        db.undo().unwrap();
+        assert_eq!(snapshot(&db), before);
+        let tasks = &snapshot(&db)[1].1;
+        assert_eq!(
+            tasks
+                .iter()
+                .map(|task| (task.order, task.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(0, "a"), (1, "b"), (2, "c"), (3, "d")]
+        );
+    }
+
+    #[test]
+    fn test_undo_sort_at_once() {
+        let mut db = setup();
+        let workspace_id = db.search_workspace_name("first").unwrap().unwrap();
+        // more tasks than the history can hold changes.
+        for i in 0..120 {
+            db.handle_add_task(AddTask {
+                name: format!("task {i:03}"),
+                workspace_id,
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        let before = snapshot(&db);
+        let mut tasks = db.get_tasks(workspace_id).unwrap();
+        tasks.sort_by(|a, b| b.name.cmp(&a.name));
+        let updates = tasks
+            .iter()
+            .enumerate()
+            .map(|(order, task)| UpdateTask {
+                id: task.id,
+                order: Some(order),
+                ..Default::default()
+            })
+            .collect();
+        db.handle_update_actions(Action::UpdateTasks(updates))
+            .unwrap();
+        let sorted = snapshot(&db);
+        assert_eq!(sorted[0].1[0].name, "task 119");
+        assert_eq!(sorted[0].1[119].name, "task 000");
+
+        assert!(db.undo().unwrap());
+        assert_eq!(snapshot(&db), before);
+        assert!(db.redo().unwrap());
+        assert_eq!(snapshot(&db), sorted);
+
+        // the change before the sort is still there to undo.
+        assert!(db.undo().unwrap());
+        assert!(db.undo().unwrap());
+        let second = db.search_workspace_name("second").unwrap().unwrap();
+        assert!(db.search_task_name("d", second).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_new_change_clears_redo() {
+        let mut db = setup();
+        db.undo().unwrap();
+        let workspace_id = db.search_workspace_name("second").unwrap().unwrap();
+        db.handle_update_actions(Action::AddTask(AddTask {
+            name: "e".into(),
+            workspace_id,
+            ..Default::default()
+        }))
+        .unwrap();
+        assert!(!db.redo().unwrap());
+    }
+
+    #[test]
+    fn test_failed_action_is_not_recorded() {
+        let mut db = setup();
+        let workspace_id = db.search_workspace_name("second").unwrap().unwrap();
+        assert!(db
+            .handle_update_actions(Action::AddTask(AddTask {
+                name: "a".into(),
+                workspace_id,
+                ..Default::default()
+            }))
+            .is_err());
+        // the last recorded change is still the task d.
+        db.undo().unwrap();
+        assert!(db.search_task_name("d", workspace_id).unwrap().is_none());
+        assert!(db.search_task_name("a", workspace_id).unwrap().is_some());
+    }
+}
+
+mod test_search {
+
+    use crate::database_ops::search::fts_query;
+    use crate::database_ops::DatabaseOperations;
+    use crate::structs::*;
+    use pretty_assertions::assert_eq;
+
+    fn setup() -> DatabaseOperations {
+        let db = DatabaseOperations::new(":memory:".into());
+        for workspace in ["work", "home"] {
+            db.handle_add_workspace(AddWorkspace {
+                name: workspace.into(),
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        let work = db.search_workspace_name("work").unwrap().unwrap();
+        let home = db.search_workspace_name("home").unwrap().unwrap();
+        for (name, description, workspace_id) in [
+            ("Quarterly report", "numbers for the board", work),
+            ("Review slides", "before the report is sent", work),
+            ("Groceries", "milk, eggs and café beans", home),
+        ] {
+            db.handle_add_task(AddTask {
+                name: name.into(),
+                description: Some(description.into()),
+                workspace_id,
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        db
+    }
+
+    fn names(db: &DatabaseOperations, query: &str) -> Vec<String> {
+        db.search_tasks(query, 10)
+            .unwrap()
+            .into_iter()
+            .map(|result| result.task.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("  "), "");
+        assert_eq!(fts_query("- ("), "");
+        assert_eq!(fts_query("board rep"), "\"board\" \"rep\"*");
+        assert_eq!(fts_query("say \"hi\" OR"), "\"say\" \"\"\"hi\"\"\" \"OR\"*");
+    }
+
+    #[test]
+    fn test_search_tasks() {
+        let db = setup();
+        // matches in the name rank before matches in the description.
+        assert_eq!(
+            names(&db, "report"),
+            vec!["Quarterly report", "Review slides"]
+        );
+        assert_eq!(names(&db, "rep"), vec!["Quarterly report", "Review slides"]);
+        assert_eq!(names(&db, "report board"), vec!["Quarterly report"]);
+        assert_eq!(names(&db, "cafe"), vec!["Groceries"]);
+        assert_eq!(names(&db, "\"NOT ("), Vec::<String>::new());
+        assert_eq!(names(&db, "( \""), Vec::<String>::new());
+
+        let result = &db.search_tasks("milk", 10).unwrap()[0];
+        assert_eq!(result.workspace, "home");
+        assert_eq!(result.snippet, "milk, eggs and café beans");
+        assert_eq!(db.search_tasks("report", 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_search_follows_changes() {
+        let db = setup();
+        let work = db.search_workspace_name("work").unwrap().unwrap();
+        let id = db.search_task_name("Review slides", work).unwrap().unwrap();
+        db.handle_update_task(UpdateTask {
+            id,
+            description: Some("nothing to see".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(names(&db, "report"), vec!["Quarterly report"]);
+        assert_eq!(names(&db, "nothing"), vec!["Review slides"]);
+
+        db.handle_remove_task(id).unwrap();
+        assert_eq!(names(&db, "nothing"), Vec::<String>::new());
+
+        db.handle_remove_workspace(work).unwrap();
+        assert_eq!(names(&db, "report"), Vec::<String>::new());
+    }
+}
+
+mod test_tags {
+
+    use super::database_with_workspace;
+    use crate::action::Action;
+    use crate::database_ops::DatabaseOperations;
+    use crate::structs::*;
+    use pretty_assertions::assert_eq;
+
+    fn tag_names(db: &DatabaseOperations) -> Vec<String> {
+        let mut stmt = db
+            .conn
+            .prepare("SELECT name FROM Tag ORDER BY name")
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .map(|name| name.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_tags() {
+        assert_eq!(
+            split_tags("fix #Bug the  login #review #bug # a#b"),
+            (
+                "fix the login # a#b".to_string(),
+                vec!["bug".to_string(), "review".to_string()]
+            )
+        );
+        assert_eq!(
+            format_tags(&["bug".into(), "review".into()]),
+            "#bug #review"
+        );
+    }
+
+    #[test]
+    fn test_task_tags() {
+        let (db, work) = database_with_workspace("work");
+        let id = db
+            .handle_add_task(AddTask {
+                name: "login".into(),
+                workspace_id: work,
+                tags: vec!["bug".into(), "review".into()],
+                ..Default::default()
+            })
+            .unwrap();
+        db.handle_add_task(AddTask {
+            name: "docs".into(),
+            workspace_id: work,
+            tags: vec!["review".into()],
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            db.get_task(id).unwrap().unwrap().tags,
+            vec!["bug", "review"]
+        );
+        assert_eq!(tag_names(&db), vec!["bug", "review"]);
+
+        db.handle_update_task(UpdateTask {
+            id,
+            tags: Some(vec!["waiting".into()]),
+            ..Default::default()
+        })
+        .unwrap();
+        let tasks = db.get_tasks(work).unwrap();
+        let tags: Vec<_> = tasks
+            .iter()
+            .map(|t| (t.name.as_str(), t.tags.clone()))
+            .collect();
+        assert!(tags.contains(&("login", vec!["waiting".to_string()])));
+        assert!(tags.contains(&("docs", vec!["review".to_string()])));
+        // the tags no task uses anymore are removed.
+        assert_eq!(tag_names(&db), vec!["review", "waiting"]);
+
+        db.handle_remove_workspace(work).unwrap();
+        assert_eq!(tag_names(&db), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_undo_tags_change() {
+        let (mut db, work) = database_with_workspace("work");
+        db.handle_update_actions(Action::AddTask(AddTask {
+            name: "login".into(),
+            workspace_id: work,
+            tags: vec!["bug".into()],
+            ..Default::default()
+        }))
+        .unwrap();
+        let id = db.search_task_name("login", work).unwrap().unwrap();
+        db.handle_update_actions(Action::UpdateTask(UpdateTask {
+            id,
+            tags: Some(Vec::new()),
+            ..Default::default()
+        }))
+        .unwrap();
+        db.handle_update_actions(Action::RemoveTask(id)).unwrap();
+
+        assert!(db.undo().unwrap());
+        assert_eq!(db.get_task(id).unwrap().unwrap().tags, Vec::<String>::new());
+        assert!(db.undo().unwrap());
+        assert_eq!(db.get_task(id).unwrap().unwrap().tags, vec!["bug"]);
+        assert!(db.undo().unwrap());
+        assert!(db.redo().unwrap());
+        assert_eq!(db.get_task(id).unwrap().unwrap().tags, vec!["bug"]);
+    }
+}
+
+mod test_subtasks {
+
+    use super::database_with_workspace;
+    use crate::action::Action;
+    use crate::database_ops::transfer::{ConflictPolicy, ImportMode};
+    use crate::database_ops::DatabaseOperations;
+    use crate::errors::DoMeError;
+    use crate::structs::*;
+    use pretty_assertions::assert_eq;
+
+    /// a workspace with the tree:
+    /// - a
+    ///   - a1
+    ///   - a2
+    /// - b
+    fn setup() -> (DatabaseOperations, i32) {
+        let (db, work) = database_with_workspace("work");
+        let a = add(&db, work, "a", None);
+        add(&db, work, "b", None);
+        add(&db, work, "a1", Some(a));
+        add(&db, work, "a2", Some(a));
+        (db, work)
+    }
+
+    fn add(db: &DatabaseOperations, workspace_id: i32, name: &str, parent_id: Option<i32>) -> i32 {
+        db.handle_add_task(AddTask {
+            name: name.into(),
+            workspace_id,
+            parent_id,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn id(db: &DatabaseOperations, work: i32, name: &str) -> i32 {
+        db.search_task_name(name, work).unwrap().unwrap()
+    }
+
+    /// the tree of the workspace as (depth, name, order).
+    fn tree(db: &DatabaseOperations, work: i32) -> Vec<(usize, String, usize)> {
+        let mut tasks = db.get_tasks(work).unwrap();
+        tasks.sort_by_key(|t| t.order);
+        task_tree(&tasks)
+            .into_iter()
+            .map(|(depth, t)| (depth, t.name.clone(), t.order))
+            .collect()
+    }
+
+    fn expected(tree: &[(usize, &str, usize)]) -> Vec<(usize, String, usize)> {
+        tree.iter()
+            .map(|&(depth, name, order)| (depth, name.to_string(), order))
+            .collect()
+    }
+
+    #[test]
+    fn test_orders_are_per_parent() {
+        let (db, work) = setup();
+        assert_eq!(
+            tree(&db, work),
+            expected(&[(0, "a", 0), (1, "a1", 0), (1, "a2", 1), (0, "b", 1)])
+        );
+
+        let a = id(&db, work, "a");
+        db.handle_add_task(AddTask {
+            name: "a0".into(),
+            workspace_id: work,
+            parent_id: Some(a),
+            order: Some(0),
+            ..Default::default()
+        })
+        .unwrap();
+        db.handle_update_task(UpdateTask {
+            id: id(&db, work, "a2"),
+            order: Some(0),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            tree(&db, work),
+            expected(&[
+                (0, "a", 0),
+                (1, "a2", 0),
+                (1, "a0", 1),
+                (1, "a1", 2),
+                (0, "b", 1)
+            ])
+        );
+
+        db.handle_remove_task(id(&db, work, "a0")).unwrap();
+        assert_eq!(
+            tree(&db, work),
+            expected(&[(0, "a", 0), (1, "a2", 0), (1, "a1", 1), (0, "b", 1)])
+        );
+    }
+
+    #[test]
+    fn test_indent_and_outdent() {
+        let (db, work) = setup();
+        let a = id(&db, work, "a");
+        let b = id(&db, work, "b");
+
+        // indenting b makes it the last subtask of a.
+        db.handle_update_task(UpdateTask {
+            id: b,
+            parent_id: Some(Some(a)),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            tree(&db, work),
+            expected(&[(0, "a", 0), (1, "a1", 0), (1, "a2", 1), (1, "b", 2)])
+        );
+
+        // outdenting a1 puts it right after a.
+        db.handle_update_task(UpdateTask {
+            id: id(&db, work, "a1"),
+            parent_id: Some(None),
+            order: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            tree(&db, work),
+            expected(&[(0, "a", 0), (1, "a2", 0), (1, "b", 1), (0, "a1", 1)])
+        );
+    }
+
+    #[test]
+    fn test_missing_parent_is_an_error() {
+        let (db, work) = setup();
+        let b = id(&db, work, "b");
+        // a parent purged from the trash breaks the foreign key, it is not a name conflict.
+        let error = db
+            .handle_update_task(UpdateTask {
+                id: b,
+                parent_id: Some(Some(b + 100)),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(error.downcast_ref::<DoMeError>().is_none());
+
+        let error = db
+            .handle_update_task(UpdateTask {
+                id: b,
+                name: Some("a".into()),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<DoMeError>(),
+            Some(DoMeError::TaskAlreadyExists(name)) if name == "a"
+        ));
+        assert_eq!(tree(&db, work)[3], (0, "b".to_string(), 1));
+    }
+
+    #[test]
+    fn test_remove_parent() {
+        let (mut db, work) = setup();
+        let a = id(&db, work, "a");
+        add(&db, work, "a11", Some(id(&db, work, "a1")));
+        assert_eq!(db.get_subtasks(a).unwrap().len(), 3);
+
+        db.handle_update_actions(Action::RemoveTask(a)).unwrap();
+        assert_eq!(tree(&db, work), expected(&[(0, "b", 0)]));
+
+        assert!(db.undo().unwrap());
+        assert_eq!(
+            tree(&db, work),
+            expected(&[
+                (0, "a", 0),
+                (1, "a1", 0),
+                (2, "a11", 0),
+                (1, "a2", 1),
+                (0, "b", 1)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_transfer_subtasks() {
+        let (db, work) = setup();
+        let document = db.export().unwrap();
+        let root = &document.workspaces[0].tasks;
+        assert_eq!(root.len(), 2);
+        assert_eq!(root[0].subtasks.len(), 2);
+
+        let db = DatabaseOperations::new(":memory:".into());
+        db.import(document, ImportMode::Replace, ConflictPolicy::Fail)
+            .unwrap();
+        let work2 = db.search_workspace_name("work").unwrap().unwrap();
+        let (db2, _) = setup();
+        assert_eq!(tree(&db, work2), tree(&db2, work));
+    }
+}
+
+mod test_recurrence {
+
+    use super::database_with_workspace;
+    use crate::action::Action;
+    use crate::database_ops::DatabaseOperations;
+    use crate::stats::Stats;
+    use crate::structs::*;
+    use chrono::{NaiveDateTime, Weekday};
+    use pretty_assertions::assert_eq;
+
+    fn date(input: &str) -> NaiveDateTime {
+        parse_date_input(input).unwrap()
+    }
+
+    #[test]
+    fn test_parse_recurrence() {
+        assert_eq!("Daily".parse(), Ok(Recurrence::Daily));
+        assert_eq!(
+            "weekly fri,Mon monday".parse(),
+            Ok(Recurrence::Weekly(vec![Weekday::Mon, Weekday::Fri]))
+        );
+        assert_eq!("every 3 days".parse(), Ok(Recurrence::AfterCompletion(3)));
+        assert!("every 0 days".parse::<Recurrence>().is_err());
+        assert!("every 4000000000 days".parse::<Recurrence>().is_err());
+        assert!("weekly someday".parse::<Recurrence>().is_err());
+        assert!("yearly".parse::<Recurrence>().is_err());
+        for rule in [
+            "daily",
+            "weekly",
+            "weekly mon,fri",
+            "monthly",
+            "every 1 day",
+        ] {
+            assert_eq!(rule.parse::<Recurrence>().unwrap().to_string(), rule);
+        }
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        // 2024-05-06 is a monday.
+        let now = date("2024-05-06 10:00");
+        let due = Some(date("2024-05-06 09:00"));
+        assert_eq!(Recurrence::Daily.next(due, now), date("2024-05-07 09:00"));
+        assert_eq!(
+            Recurrence::Weekly(vec![Weekday::Mon, Weekday::Fri]).next(due, now),
+            date("2024-05-10 09:00")
+        );
+        assert_eq!(
+            Recurrence::Weekly(Vec::new()).next(due, now),
+            date("2024-05-13 09:00")
+        );
+        assert_eq!(
+            Recurrence::AfterCompletion(3).next(Some(date("2024-04-01")), now),
+            date("2024-05-09")
+        );
+        assert_eq!(
+            Recurrence::AfterCompletion(u32::MAX).next(None, now).date(),
+            chrono::NaiveDate::MAX
+        );
+        assert_eq!(Recurrence::Daily.next(None, now), date("2024-05-07"));
+        // the missed occurrences are skipped.
+        assert_eq!(
+            Recurrence::Daily.next(Some(date("2024-04-01")), now),
+            date("2024-05-07")
+        );
+        // a monthly task due on the 31st stays on the last day of the shorter months.
+        assert_eq!(
+            Recurrence::Monthly.next(Some(date("2024-01-31")), date("2024-01-31")),
+            date("2024-02-29")
+        );
+        assert_eq!(
+            Recurrence::Monthly.next(Some(date("2024-01-31")), date("2024-03-01")),
+            date("2024-03-31")
+        );
+    }
+
+    #[test]
+    fn test_complete_recurring_task() {
+        let (mut db, work) = database_with_workspace("work");
+        let id = db
+            .handle_add_task(AddTask {
+                name: "weekly report".into(),
+                workspace_id: work,
+                due_date: Some(date("2024-05-06")),
+                start_date: Some(date("2024-05-03")),
+                recurrence: Some(Recurrence::Weekly(Vec::new())),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let task = db.get_task(id).unwrap().unwrap();
+        assert_eq!(task.recurrence, Some(Recurrence::Weekly(Vec::new())));
+        let update = task.toggle_completion(date("2024-05-06 10:00"));
+        db.handle_update_actions(Action::UpdateTask(update))
+            .unwrap();
+        let task = db.get_task(id).unwrap().unwrap();
+        assert!(!task.completed);
+        assert_eq!(task.due_date, Some(date("2024-05-13")));
+        assert_eq!(task.start_date, Some(date("2024-05-10")));
+
+        assert!(db.undo().unwrap());
+        assert_eq!(
+            db.get_task(id).unwrap().unwrap().due_date,
+            Some(date("2024-05-06"))
+        );
+
+        // a task that is not recurring anymore is completed.
+        db.handle_update_task(UpdateTask {
+            id,
+            recurrence: Some(None),
+            ..Default::default()
+        })
+        .unwrap();
+        let task = db.get_task(id).unwrap().unwrap();
+        assert_eq!(task.recurrence, None);
+        db.handle_update_task(task.toggle_completion(date("2024-05-06 10:00")))
+            .unwrap();
+        assert!(db.get_task(id).unwrap().unwrap().completed);
+    }
+
+    #[test]
+    fn test_undo_recurring_completion() {
+        let (mut db, work) = database_with_workspace("work");
+        let id = db
+            .handle_add_task(AddTask {
+                name: "water the plants".into(),
+                workspace_id: work,
+                due_date: Some(date("2024-05-06")),
+                recurrence: Some(Recurrence::Daily),
+                ..Default::default()
+            })
+            .unwrap();
+        let complete = |db: &mut DatabaseOperations, completed_at| {
+            let task = db.get_task(id).unwrap().unwrap();
+            db.handle_update_actions(Action::UpdateTask(
+                task.toggle_completion(date(completed_at)),
+            ))
+            .unwrap();
+        };
+        complete(&mut db, "2024-05-06 10:00");
+        complete(&mut db, "2024-05-07 10:00");
+        // the days of the last week with a completion.
+        let completed_days = |db: &DatabaseOperations| {
+            Stats::collect(db, date("2024-05-08 20:00"), 7)
+                .unwrap()
+                .completed_per_day
+                .into_iter()
+                .filter(|(_, count)| *count > 0)
+                .collect::<Vec<_>>()
+        };
+        let day = |input| date(input).date();
+
+        assert!(db.undo().unwrap());
+        assert_eq!(
+            db.get_task(id).unwrap().unwrap().completed_at,
+            Some(date("2024-05-06 10:00"))
+        );
+        let kinds: Vec<TaskEventKind> = db
+            .get_task_events(id)
+            .unwrap()
+            .iter()
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TaskEventKind::Reopened,
+                TaskEventKind::Created,
+                TaskEventKind::Completed,
+                TaskEventKind::Completed,
+            ]
+        );
+        assert_eq!(completed_days(&db), vec![(day("2024-05-06"), 1)]);
+
+        // going back and forth counts each completion once.
+        assert!(db.redo().unwrap());
+        assert!(db.undo().unwrap());
+        assert!(db.redo().unwrap());
+        assert_eq!(
+            completed_days(&db),
+            vec![(day("2024-05-06"), 1), (day("2024-05-07"), 1)]
+        );
+
+        // undoing the first completion leaves none.
+        assert!(db.undo().unwrap());
+        assert!(db.undo().unwrap());
+        assert_eq!(db.get_task(id).unwrap().unwrap().completed_at, None);
+        assert_eq!(completed_days(&db), vec![]);
+    }
+}
+
+mod test_trash {
+
+    use super::database_with_workspace;
+    use crate::action::Action;
+    use crate::database_ops::DatabaseOperations;
+    use crate::errors::DoMeError;
+    use crate::structs::*;
+    use pretty_assertions::assert_eq;
+
+    fn setup() -> (DatabaseOperations, i32) {
+        let (mut db, workspace_id) = database_with_workspace("work");
+        for name in ["a", "b", "c", "d"] {
+            db.handle_update_actions(Action::AddTask(AddTask {
+                name: name.into(),
+                workspace_id,
+                ..Default::default()
+            }))
+            .unwrap();
+        }
+        let parent_id = db.search_task_name("b", workspace_id).unwrap().unwrap();
+        db.handle_update_actions(Action::AddTask(AddTask {
+            name: "b1".into(),
+            workspace_id,
+            parent_id: Some(parent_id),
+            ..Default::default()
+        }))
+        .unwrap();
+        (db, workspace_id)
+    }
+
+    fn task_names(db: &DatabaseOperations, workspace_id: i32) -> Vec<(usize, String)> {
+        let mut tasks = db.get_tasks(workspace_id).unwrap();
+        TaskSorter::default().sort(&mut tasks);
+        task_tree(&tasks)
+            .into_iter()
+            .map(|(depth, task)| (task.order, "  ".repeat(depth) + &task.name))
+            .collect()
+    }
+
+    fn trash_task(db: &mut DatabaseOperations, workspace_id: i32, name: &str) {
+        let id = db.search_task_name(name, workspace_id).unwrap().unwrap();
+        db.handle_update_actions(Action::RemoveTask(id)).unwrap();
+    }
+
+    #[test]
+    fn test_trash_and_restore_task() {
+        let (mut db, workspace_id) = setup();
+        let before = task_names(&db, workspace_id);
+        trash_task(&mut db, workspace_id, "b");
+        assert_eq!(
+            task_names(&db, workspace_id),
+            vec![(0, "a".into()), (1, "c".into()), (2, "d".into())]
+        );
+
+        let trash = db.get_trash().unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].name(), "b");
+        assert_eq!(trash[0].task_count(), 1);
+
+        db.handle_update_actions(Action::RestoreTrashItem(trash[0].id))
+            .unwrap();
+        assert_eq!(task_names(&db, workspace_id), before);
+        assert!(db.get_trash().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_keeps_orders_contiguous() {
+        let (mut db, workspace_id) = setup();
+        trash_task(&mut db, workspace_id, "d");
+        trash_task(&mut db, workspace_id, "c");
+        let d = db.get_trash().unwrap().pop().unwrap();
+        assert_eq!(d.name(), "d");
+        db.handle_update_actions(Action::RestoreTrashItem(d.id))
+            .unwrap();
+        assert_eq!(
+            task_names(&db, workspace_id),
+            vec![
+                (0, "a".into()),
+                (1, "b".into()),
+                (0, "  b1".into()),
+                (2, "d".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_restore_subtask_without_parent() {
+        let (mut db, workspace_id) = setup();
+        trash_task(&mut db, workspace_id, "b1");
+        trash_task(&mut db, workspace_id, "b");
+        let b1 = db.get_trash().unwrap().pop().unwrap();
+        db.handle_update_actions(Action::RestoreTrashItem(b1.id))
+            .unwrap();
+        let b1 = db.search_task_name("b1", workspace_id).unwrap().unwrap();
+        assert_eq!(db.get_task(b1).unwrap().unwrap().parent_id, None);
+    }
+
+    #[test]
+    fn test_restore_taken_name_fails() {
+        let (mut db, workspace_id) = setup();
+        trash_task(&mut db, workspace_id, "a");
+        db.handle_update_actions(Action::AddTask(AddTask {
+            name: "a".into(),
+            workspace_id,
+            ..Default::default()
+        }))
+        .unwrap();
+        let item = db.get_trash().unwrap().pop().unwrap();
+        let error = db
+            .handle_update_actions(Action::RestoreTrashItem(item.id))
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<DoMeError>(),
+            Some(DoMeError::TaskAlreadyExists(_))
+        ));
+        assert_eq!(db.get_trash().unwrap(), vec![item]);
+    }
+
+    #[test]
+    fn test_trash_workspace_and_undo() {
+        let (mut db, workspace_id) = setup();
+        let before = task_names(&db, workspace_id);
+        db.handle_update_actions(Action::RemoveWorkspace(workspace_id))
+            .unwrap();
+        assert!(db.get_workspaces().unwrap().is_empty());
+        let item = db.get_trash().unwrap().pop().unwrap();
+        assert_eq!(item.task_count(), 5);
+
+        db.handle_update_actions(Action::RestoreTrashItem(item.id))
+            .unwrap();
+        assert_eq!(task_names(&db, workspace_id), before);
+
+        // undoing the restore puts the workspace back in the trash as it was.
+        assert!(db.undo().unwrap());
+        assert!(db.get_workspaces().unwrap().is_empty());
+        assert_eq!(db.get_trash().unwrap(), vec![item.clone()]);
+        assert!(db.redo().unwrap());
+        assert_eq!(task_names(&db, workspace_id), before);
+    }
+
+    #[test]
+    fn test_purge_and_empty_trash() {
+        let (mut db, workspace_id) = setup();
+        for name in ["a", "c", "d"] {
+            trash_task(&mut db, workspace_id, name);
+        }
+        let trash = db.get_trash().unwrap();
+        assert_eq!(
+            trash.iter().map(TrashItem::name).collect::<Vec<_>>(),
+            vec!["d", "c", "a"]
+        );
+        db.handle_update_actions(Action::PurgeTrashItem(trash[0].id))
+            .unwrap();
+        assert_eq!(db.get_trash().unwrap(), trash[1..]);
+        db.handle_update_actions(Action::EmptyTrash).unwrap();
+        assert!(db.get_trash().unwrap().is_empty());
+
+        assert!(db.undo().unwrap());
+        assert_eq!(db.get_trash().unwrap(), trash[1..]);
+    }
+}
+
+mod test_status {
+
+    use super::database_with_workspace;
+    use crate::action::Action;
+    use crate::structs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_task_status() {
+        let (mut db, work) = database_with_workspace("work");
+        let id = db
+            .handle_add_task(AddTask {
+                name: "report".into(),
+                workspace_id: work,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(db.get_task(id).unwrap().unwrap().status, None);
+
+        db.handle_update_actions(Action::UpdateTask(UpdateTask {
+            id,
+            status: Some(Some("blocked".into())),
+            ..Default::default()
+        }))
+        .unwrap();
+        let task = db.get_task(id).unwrap().unwrap();
+        assert_eq!(task.status.as_deref(), Some("blocked"));
+
+        // checking the task leaves the status it had before.
+        db.handle_update_actions(Action::UpdateTask(UpdateTask {
+            id,
+            completed: Some(true),
+            ..Default::default()
+        }))
+        .unwrap();
+        let done = db.get_task(id).unwrap().unwrap();
+        assert!(done.completed);
+        assert_eq!(done.status, None);
+
+        assert!(db.undo().unwrap());
+        assert_eq!(db.get_task(id).unwrap().unwrap(), task);
+
+        // a completion that does not change keeps the status.
+        db.handle_update_task(UpdateTask {
+            id,
+            completed: Some(false),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(db.get_task(id).unwrap().unwrap(), task);
+    }
+}
+
+mod test_task_events {
+
+    use super::database_with_workspace;
+    use crate::action::Action;
+    use crate::database_ops::transfer::{ConflictPolicy, ImportMode};
+    use crate::database_ops::DatabaseOperations;
+    use crate::structs::*;
+    use pretty_assertions::assert_eq;
+
+    fn kinds(events: &[TaskEvent]) -> Vec<TaskEventKind> {
+        events.iter().map(|event| event.kind).collect()
+    }
+
+    #[test]
+    fn test_task_events() {
+        let (mut db, work) = database_with_workspace("work");
+        let project = db
+            .handle_add_task(AddTask {
+                name: "project".into(),
+                workspace_id: work,
+                ..Default::default()
+            })
+            .unwrap();
+        let id = db
+            .handle_add_task(AddTask {
+                name: "report".into(),
+                workspace_id: work,
+                ..Default::default()
+            })
+            .unwrap();
+        db.handle_update_actions(Action::UpdateTask(UpdateTask {
+            id,
+            name: Some("final report".into()),
+            priority: Some(1),
+            parent_id: Some(Some(project)),
+            ..Default::default()
+        }))
+        .unwrap();
+        let task = db.get_task(id).unwrap().unwrap();
+        // the completion is written at its date, which comes last here.
+        let completed_at = db.get_task_events(id).unwrap()[0].created_at;
+        db.handle_update_actions(Action::UpdateTask(task.toggle_completion(completed_at)))
+            .unwrap();
+        assert_eq!(
+            db.get_task(id).unwrap().unwrap().completed_at,
+            Some(completed_at)
+        );
+
+        let events = db.get_task_events(id).unwrap();
+        assert_eq!(
+            kinds(&events),
+            vec![
+                TaskEventKind::Completed,
+                TaskEventKind::Moved,
+                TaskEventKind::Reprioritized,
+                TaskEventKind::Renamed,
+                TaskEventKind::Created,
+            ]
+        );
+        assert_eq!(events[0].task_name, "final report");
+        assert_eq!(events[0].workspace.as_deref(), Some("work"));
+        assert_eq!(events[1].old_value, None);
+        assert_eq!(events[1].new_value.as_deref(), Some("project"));
+        assert_eq!(events[2].old_value.as_deref(), Some("C"));
+        assert_eq!(events[2].new_value.as_deref(), Some("A"));
+        assert_eq!(events[3].old_value.as_deref(), Some("report"));
+
+        // undoing the completion reopens the task, which is part of its history too.
+        assert!(db.undo().unwrap());
+        assert_eq!(db.get_task(id).unwrap().unwrap(), task);
+        assert_eq!(
+            db.get_task_events(id).unwrap()[0].kind,
+            TaskEventKind::Reopened
+        );
+
+        // the history outlives the task.
+        db.trash_task(id).unwrap();
+        assert_eq!(db.get_task_events(id).unwrap().len(), 6);
+        let since = db.get_task_events(project).unwrap()[0].created_at;
+        assert_eq!(db.get_events_since(Some(since)).unwrap().len(), 7);
+        assert!(db
+            .get_events_since(parse_date_input("2999-01-01"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_imported_events() {
+        let db = DatabaseOperations::new(":memory:".into());
+        let document = ExportDocument {
+            version: 1,
+            workspaces: vec![ExportWorkspace {
+                name: "work".into(),
+                tasks: vec![ExportTask {
+                    name: "report".into(),
+                    priority: 2,
+                    completed: true,
+                    create_date: parse_date_input("2024-05-01 09:00"),
+                    completed_at: parse_date_input("2024-05-03 17:00"),
+                    ..Default::default()
+                }],
+            }],
+            ..Default::default()
+        };
+        db.import(document, ImportMode::Merge, ConflictPolicy::Fail)
+            .unwrap();
+
+        // the history keeps the exported dates instead of the date of the import.
+        let events = db.get_events_since(parse_date_input("2024-05-01")).unwrap();
+        let dates: Vec<_> = events
+            .iter()
+            .map(|event| (event.kind, event.created_at))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![
+                (
+                    TaskEventKind::Completed,
+                    parse_date_input("2024-05-03 17:00").unwrap()
+                ),
+                (
+                    TaskEventKind::Created,
+                    parse_date_input("2024-05-01 09:00").unwrap()
+                ),
+            ]
+        );
+        assert!(db
+            .get_events_since(parse_date_input("2024-05-04"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_recurring_completion() {
+        let (db, home) = database_with_workspace("home");
+        let id = db
+            .handle_add_task(AddTask {
+                name: "water the plants".into(),
+                workspace_id: home,
+                create_date: parse_date_input("2024-05-01"),
+                due_date: parse_date_input("2024-05-06"),
+                recurrence: "daily".parse().ok(),
+                ..Default::default()
+            })
+            .unwrap();
+        let completed_at = parse_date_input("2024-05-06 09:00").unwrap();
+        let task = db.get_task(id).unwrap().unwrap();
+        db.handle_update_task(task.toggle_completion(completed_at))
+            .unwrap();
+
+        // the task moves on to its next occurrence and keeps the date of the completed one.
+        let task = db.get_task(id).unwrap().unwrap();
+        assert!(!task.completed);
+        assert_eq!(task.due_date, parse_date_input("2024-05-07"));
+        assert_eq!(task.completed_at, Some(completed_at));
+        assert_eq!(
+            kinds(&db.get_task_events(id).unwrap()),
+            vec![TaskEventKind::Completed, TaskEventKind::Created]
+        );
+    }
 }
@@ -0,0 +1,191 @@
+use super::DatabaseOperations;
+use crate::errors::DoMeError;
+use crate::structs::*;
+use chrono::Utc;
+use clap::ValueEnum;
+use color_eyre::Result;
+
+/// version of the export document written by `export`.
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ImportMode {
+    /// Add the imported workspaces and tasks to the existing ones
+    #[default]
+    Merge,
+    /// Remove everything before importing
+    Replace,
+}
+
+/// What to do with an imported task when its workspace already has a task with the same name.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ConflictPolicy {
+    /// Keep the existing task
+    #[default]
+    Skip,
+    /// Update the existing task with the imported one
+    Overwrite,
+    /// Abort the import without changing anything
+    Fail,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub workspaces: usize,
+    pub tasks: usize,
+    pub skipped: usize,
+    pub overwritten: usize,
+}
+
+impl DatabaseOperations {
+    pub fn export(&self) -> Result<ExportDocument> {
+        let mut workspaces = self.get_workspaces()?;
+        WorkspaceSorter::default().sort(&mut workspaces);
+        let mut document = ExportDocument {
+            version: EXPORT_VERSION,
+            exported_at: Utc::now().naive_utc(),
+            workspaces: Vec::with_capacity(workspaces.len()),
+        };
+        for workspace in workspaces {
+            let tasks = self.get_tasks(workspace.id)?;
+            document.workspaces.push(ExportWorkspace {
+                name: workspace.name,
+                tasks: export_tasks(&tasks, None),
+            });
+        }
+        Ok(document)
+    }
+
+    /// Imports a document in a single transaction, nothing is changed if it fails.
+    pub fn import(
+        &self,
+        document: ExportDocument,
+        mode: ImportMode,
+        on_conflict: ConflictPolicy,
+    ) -> Result<ImportSummary> {
+        if document.version > EXPORT_VERSION {
+            return Err(DoMeError::UnsupportedExportVersion(document.version).into());
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        let mut summary = ImportSummary::default();
+
+        if mode == ImportMode::Replace {
+            for workspace in self.get_workspaces()? {
+                self.handle_remove_workspace(workspace.id)?;
+            }
+        }
+
+        for workspace in document.workspaces {
+            let name = workspace.name.trim().to_string();
+            match self.handle_add_workspace(AddWorkspace {
+                name: name.clone(),
+                ..Default::default()
+            }) {
+                Ok(()) => summary.workspaces += 1,
+                // merging into the existing workspace.
+                Err(e)
+                    if matches!(e.downcast_ref(), Some(DoMeError::WorkspaceAlreadyExists(_))) => {}
+                Err(e) => return Err(e),
+            }
+            let workspace_id = self
+                .search_workspace_name(&name)?
+                .expect("the workspace was just added or already existed");
+
+            for task in workspace.tasks {
+                self.import_task(task, workspace_id, None, on_conflict, &mut summary)?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(summary)
+    }
+
+    /// imports a task and its subtasks, the subtasks of a skipped task go under the existing one.
+    fn import_task(
+        &self,
+        task: ExportTask,
+        workspace_id: i32,
+        parent_id: Option<i32>,
+        on_conflict: ConflictPolicy,
+        summary: &mut ImportSummary,
+    ) -> Result<()> {
+        let name = task.name.trim().to_string();
+        let priority = task.priority.clamp(1, 4);
+        let update = match self.handle_add_task(AddTask {
+            name: name.clone(),
+            description: Some(task.description.clone()),
+            priority: Some(priority),
+            due_date: task.due_date,
+            start_date: task.start_date,
+            create_date: task.create_date,
+            workspace_id,
+            tags: normalize_tags(task.tags.clone()),
+            parent_id,
+            recurrence: task.recurrence.clone(),
+            ..Default::default()
+        }) {
+            Ok(_) => {
+                summary.tasks += 1;
+                true
+            }
+            Err(e) => match (e.downcast_ref(), on_conflict) {
+                (Some(DoMeError::TaskAlreadyExists(_)), ConflictPolicy::Skip) => {
+                    summary.skipped += 1;
+                    false
+                }
+                (Some(DoMeError::TaskAlreadyExists(_)), ConflictPolicy::Overwrite) => {
+                    summary.overwritten += 1;
+                    true
+                }
+                _ => return Err(e),
+            },
+        };
+
+        let id = self
+            .search_task_name(&name, workspace_id)?
+            .expect("the task was just added or already existed");
+        if update {
+            self.handle_update_task(UpdateTask {
+                id,
+                description: Some(task.description),
+                priority: Some(priority),
+                completed: Some(task.completed),
+                due_date: Some(task.due_date),
+                start_date: Some(task.start_date),
+                tags: Some(normalize_tags(task.tags)),
+                recurrence: Some(task.recurrence),
+                status: Some(task.status),
+                // an export without the date of a completion does not date it to the import.
+                completed_at: Some(task.completed_at),
+                ..Default::default()
+            })?;
+        }
+        for subtask in task.subtasks {
+            self.import_task(subtask, workspace_id, Some(id), on_conflict, summary)?;
+        }
+        Ok(())
+    }
+}
+
+/// the tasks under `parent_id` in their order, each with its own subtasks.
+fn export_tasks(tasks: &[Task], parent_id: Option<i32>) -> Vec<ExportTask> {
+    let mut children: Vec<&Task> = tasks.iter().filter(|t| t.parent_id == parent_id).collect();
+    children.sort_by_key(|task| task.order);
+    children
+        .into_iter()
+        .map(|task| ExportTask {
+            name: task.name.clone(),
+            description: task.description.clone(),
+            priority: task.priority,
+            completed: task.completed,
+            create_date: Some(task.create_date),
+            due_date: task.due_date,
+            start_date: task.start_date,
+            tags: task.tags.clone(),
+            recurrence: task.recurrence.clone(),
+            status: task.status.clone(),
+            completed_at: task.completed_at,
+            subtasks: export_tasks(tasks, Some(task.id)),
+        })
+        .collect()
+}
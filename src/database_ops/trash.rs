@@ -0,0 +1,173 @@
+use super::output::parse_datetime;
+use super::DatabaseOperations;
+use crate::errors::DoMeError;
+use crate::structs::*;
+use color_eyre::{eyre::Ok, Result};
+use rusqlite::{params, types::Type, Error, OptionalExtension, Row};
+use std::cmp::min;
+
+fn parse_trash_item(row: &Row) -> rusqlite::Result<TrashItem> {
+    let content: String = row.get(1)?;
+    std::result::Result::Ok(TrashItem {
+        id: row.get(0)?,
+        content: serde_json::from_str(&content)
+            .map_err(|e| Error::FromSqlConversionFailure(1, Type::Text, Box::new(e)))?,
+        deleted_at: parse_datetime(row, 2)?,
+    })
+}
+
+impl DatabaseOperations {
+    /// the removed tasks and workspaces, the last removed first.
+    pub fn get_trash(&self) -> Result<Vec<TrashItem>> {
+        const GET_TRASH_QUERY: &str =
+            "SELECT id, content, deleted_at FROM Trash ORDER BY deleted_at DESC, id DESC";
+        let mut stmt = self.conn.prepare(GET_TRASH_QUERY)?;
+        let items = stmt.query_map([], parse_trash_item)?;
+        Ok(items.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn get_trash_item(&self, id: i32) -> Result<Option<TrashItem>> {
+        const GET_TRASH_ITEM_QUERY: &str = "SELECT id, content, deleted_at FROM Trash WHERE id = ?";
+        Ok(self
+            .conn
+            .query_row(GET_TRASH_ITEM_QUERY, params![id], parse_trash_item)
+            .optional()?)
+    }
+
+    /// removes a task and its subtasks and keeps them in the trash.
+    pub fn trash_task(&self, id: i32) -> Result<Option<TrashItem>> {
+        let Some(task) = self.get_task(id)? else {
+            return Ok(None);
+        };
+        let workspace = self
+            .get_workspace(task.workspace_id)?
+            .map(|workspace| workspace.name)
+            .unwrap_or_default();
+        let content = TrashContent::Task {
+            subtasks: self.get_subtasks(id)?,
+            task,
+            workspace,
+        };
+        self.handle_remove_task(id)?;
+        self.add_trash_content(&content).map(Some)
+    }
+
+    /// removes a workspace and its tasks and keeps them in the trash.
+    pub fn trash_workspace(&self, id: i32) -> Result<Option<TrashItem>> {
+        let Some(workspace) = self.get_workspace(id)? else {
+            return Ok(None);
+        };
+        let content = TrashContent::Workspace {
+            tasks: self.get_tasks(id)?,
+            workspace,
+        };
+        self.handle_remove_workspace(id)?;
+        self.add_trash_content(&content).map(Some)
+    }
+
+    fn add_trash_content(&self, content: &TrashContent) -> Result<TrashItem> {
+        const ADD_TRASH_QUERY: &str = "INSERT INTO Trash (content) VALUES (?)";
+        self.conn
+            .execute(ADD_TRASH_QUERY, params![serde_json::to_string(content)?])?;
+        let id = self.conn.last_insert_rowid() as i32;
+        Ok(self.get_trash_item(id)?.expect("the item was just added"))
+    }
+
+    /// puts a restored item back in the trash with its id and removal date.
+    pub(super) fn put_back_in_trash(&self, item: &TrashItem) -> Result<()> {
+        match &item.content {
+            TrashContent::Task { task, .. } => self.handle_remove_task(task.id)?,
+            TrashContent::Workspace { workspace, .. } => {
+                self.handle_remove_workspace(workspace.id)?
+            }
+        }
+        self.insert_trash_item(item)
+    }
+
+    pub(super) fn insert_trash_item(&self, item: &TrashItem) -> Result<()> {
+        const INSERT_TRASH_QUERY: &str =
+            "INSERT INTO Trash (id, content, deleted_at) VALUES (?, ?, ?)";
+        self.conn.execute(
+            INSERT_TRASH_QUERY,
+            params![
+                item.id,
+                serde_json::to_string(&item.content)?,
+                item.deleted_at.format(DATETIME_FORMAT).to_string()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// brings an item back where it was and takes it out of the trash. the names must still be
+    /// free, a task whose parent is gone is restored at the top level and the orders are kept
+    /// within the current ones.
+    pub fn restore_trash_item(&self, item: &TrashItem) -> Result<()> {
+        match &item.content {
+            TrashContent::Task {
+                task,
+                subtasks,
+                workspace,
+            } => {
+                if self.get_workspace(task.workspace_id)?.is_none() {
+                    return Err(DoMeError::WorkspaceNotFound(workspace.clone()).into());
+                }
+                for task in std::iter::once(task).chain(subtasks) {
+                    if self
+                        .search_task_name(&task.name, task.workspace_id)?
+                        .is_some()
+                    {
+                        return Err(DoMeError::TaskAlreadyExists(task.name.clone()).into());
+                    }
+                }
+                let parent_id = match task.parent_id {
+                    Some(parent_id) => self.get_task(parent_id)?.map(|parent| parent.id),
+                    None => None,
+                };
+                let siblings = self.count_siblings(task.workspace_id, parent_id)?;
+                self.restore_task(&Task {
+                    parent_id,
+                    order: min(task.order, siblings),
+                    ..task.clone()
+                })?;
+                self.restore_tasks(subtasks)?;
+            }
+            TrashContent::Workspace { workspace, tasks } => {
+                if self.search_workspace_name(&workspace.name)?.is_some() {
+                    return Err(DoMeError::WorkspaceAlreadyExists(workspace.name.clone()).into());
+                }
+                let workspaces = self.count_workspaces()?;
+                self.restore_workspace(&Workspace {
+                    order: min(workspace.order, workspaces),
+                    ..workspace.clone()
+                })?;
+                self.restore_tasks(tasks)?;
+            }
+        }
+        self.purge_trash_items(std::slice::from_ref(item))
+    }
+
+    pub fn purge_trash_items(&self, items: &[TrashItem]) -> Result<()> {
+        const PURGE_TRASH_QUERY: &str = "DELETE FROM Trash WHERE id = ?";
+        for item in items {
+            self.conn.execute(PURGE_TRASH_QUERY, params![item.id])?;
+        }
+        Ok(())
+    }
+
+    fn count_siblings(&self, workspace_id: i32, parent_id: Option<i32>) -> Result<usize> {
+        const COUNT_SIBLINGS_QUERY: &str =
+            "SELECT COUNT(*) FROM Task WHERE workspaceid = ? AND parentid IS ?";
+        Ok(self.conn.query_row(
+            COUNT_SIBLINGS_QUERY,
+            params![workspace_id, parent_id],
+            |row| row.get(0),
+        )?)
+    }
+
+    fn count_workspaces(&self) -> Result<usize> {
+        const COUNT_WORKSPACES_QUERY: &str = "SELECT COUNT(*) FROM Workspace";
+        Ok(self
+            .conn
+            .query_row(COUNT_WORKSPACES_QUERY, [], |row| row.get(0))?)
+    }
+}
@@ -1,6 +1,7 @@
-use super::DatabaseOperations;
+use super::{is_unique_violation, DatabaseOperations};
 use crate::errors::DoMeError;
 use crate::structs::*;
+use chrono::Local;
 use color_eyre::{eyre::Ok, Result};
 use rusqlite::{params, Error};
 
@@ -12,48 +13,103 @@ impl DatabaseOperations {
             UPDATE_WORKSPACE_QUERY,
             params![info.name, info.order, info.id],
         ) {
-            Err(Error::SqliteFailure(e, _)) => {
-                if e.code == rusqlite::ErrorCode::ConstraintViolation {
-                    Err(DoMeError::WorkspaceAlreadyExists(
-                        info.name
-                            .expect("the name is not none since it exist in the database"),
-                    )
-                    .into())
-                } else {
-                    Err(e.into())
-                }
-            }
+            // only a new name can clash with the name of another workspace.
+            Err(Error::SqliteFailure(e, message)) if is_unique_violation(&e) => match info.name {
+                Some(name) => Err(DoMeError::WorkspaceAlreadyExists(name).into()),
+                None => Err(Error::SqliteFailure(e, message).into()),
+            },
             Err(e) => Err(e.into()),
             _ => Ok(()),
         }
     }
 
+    /// updates a task, what changed is written in its history.
     pub fn handle_update_task(&self, info: UpdateTask) -> Result<()> {
-        const UPDATE_TASK_QUERY: &str = "UPDATE Task SET name = COALESCE(?, name), task_order = COALESCE(?, task_order), description = COALESCE(?, description), priority = COALESCE(?, priority), completed = COALESCE(?, completed) WHERE id = ?";
+        const UPDATE_TASK_QUERY: &str = "UPDATE Task SET name = COALESCE(?, name), parentid = CASE WHEN ? THEN ? ELSE parentid END, task_order = COALESCE(?, task_order), description = COALESCE(?, description), priority = COALESCE(?, priority), completed = COALESCE(?, completed), status = CASE WHEN ? THEN ? WHEN ? IS NOT NULL AND ? != completed THEN NULL ELSE status END, completed_at = CASE WHEN ? THEN ? WHEN ? = 1 AND completed = 0 THEN ? WHEN ? = 0 THEN NULL ELSE completed_at END, due_date = CASE WHEN ? THEN ? ELSE due_date END, start_date = CASE WHEN ? THEN ? ELSE start_date END, recurrence = CASE WHEN ? THEN ? ELSE recurrence END WHERE id = ?";
+        // a task moved to another parent goes last among its new siblings by default.
+        let order = match (info.parent_id, info.order) {
+            (Some(parent_id), None) => Some(self.count_subtasks(info.id, parent_id)?),
+            (_, order) => order,
+        };
+        let before = self.get_task(info.id)?;
+        let now = Local::now().naive_local();
         match self.conn.execute(
             UPDATE_TASK_QUERY,
             params![
                 info.name,
-                info.order,
+                info.parent_id.is_some(),
+                info.parent_id.flatten(),
+                order,
                 info.description,
                 info.priority,
                 info.completed,
+                info.status.is_some(),
+                info.status.as_ref().and_then(Option::as_deref),
+                info.completed,
+                info.completed,
+                info.completed_at.is_some(),
+                info.completed_at
+                    .flatten()
+                    .map(|date| date.format(DATETIME_FORMAT).to_string()),
+                info.completed,
+                now.format(DATETIME_FORMAT).to_string(),
+                info.completed,
+                info.due_date.is_some(),
+                info.due_date
+                    .flatten()
+                    .map(|date| date.format(DATETIME_FORMAT).to_string()),
+                info.start_date.is_some(),
+                info.start_date
+                    .flatten()
+                    .map(|date| date.format(DATETIME_FORMAT).to_string()),
+                info.recurrence.is_some(),
+                info.recurrence
+                    .as_ref()
+                    .and_then(|recurrence| recurrence.as_ref().map(Recurrence::to_string)),
                 info.id
             ],
         ) {
-            Err(Error::SqliteFailure(e, _)) => {
-                if e.code == rusqlite::ErrorCode::ConstraintViolation {
-                    Err(DoMeError::TaskAlreadyExists(
-                        info.name
-                            .expect("the name is not none since it exist in the database"),
-                    )
-                    .into())
-                } else {
-                    Err(e.into())
+            // only a new name can clash with the name of another task, a missing parent breaks
+            // the foreign key and is reported as it is.
+            Err(Error::SqliteFailure(e, message)) if is_unique_violation(&e) => match info.name {
+                Some(name) => Err(DoMeError::TaskAlreadyExists(name).into()),
+                None => Err(Error::SqliteFailure(e, message).into()),
+            },
+            Err(e) => Err(e.into()),
+            _ => {
+                if let Some(tags) = info.tags {
+                    self.set_task_tags(info.id, &tags)?;
+                }
+                match (before, self.get_task(info.id)?) {
+                    (Some(before), Some(after)) => self.add_task_changes(&before, &after, now),
+                    _ => Ok(()),
                 }
             }
-            Err(e) => Err(e.into()),
-            _ => Ok(()),
         }
     }
+
+    /// the number of tasks under `parent_id` in the workspace of the task `id`, not counting it.
+    fn count_subtasks(&self, id: i32, parent_id: Option<i32>) -> Result<usize> {
+        const COUNT_SUBTASKS_QUERY: &str = "SELECT COUNT(*) FROM Task WHERE parentid IS ? AND workspaceid = (SELECT workspaceid FROM Task WHERE id = ?) AND id != ?";
+        Ok(self
+            .conn
+            .query_row(COUNT_SUBTASKS_QUERY, params![parent_id, id, id], |row| {
+                row.get(0)
+            })?)
+    }
+
+    /// replaces the tags of a task, the tags no task uses anymore are removed.
+    pub(super) fn set_task_tags(&self, task_id: i32, tags: &[String]) -> Result<()> {
+        const CLEAR_TASK_TAGS_QUERY: &str = "DELETE FROM TaskTag WHERE taskid = ?";
+        const ADD_TAG_QUERY: &str = "INSERT OR IGNORE INTO Tag (name) VALUES (?)";
+        const ADD_TASK_TAG_QUERY: &str =
+            "INSERT OR IGNORE INTO TaskTag (taskid, tagid) SELECT ?, id FROM Tag WHERE name = ?";
+        self.conn.execute(CLEAR_TASK_TAGS_QUERY, params![task_id])?;
+        for tag in tags {
+            self.conn.execute(ADD_TAG_QUERY, params![tag])?;
+            self.conn
+                .execute(ADD_TASK_TAG_QUERY, params![task_id, tag])?;
+        }
+        Ok(())
+    }
 }
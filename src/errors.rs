@@ -6,23 +6,45 @@ use tracing::error;
 
 #[derive(Debug)]
 pub enum DoMeError {
-    // TaskNotFound(String),
-    // WorkspaceNotFound(String),
+    TaskNotFound(String),
+    WorkspaceNotFound(String),
+    AmbiguousTask(String),
     TaskAlreadyExists(String),
     WorkspaceAlreadyExists(String),
+    TrashItemNotFound(i32),
+    UnsupportedDatabaseVersion(i32),
+    UnsupportedExportVersion(u32),
 }
 
 impl fmt::Display for DoMeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            // DoMeError::TaskNotFound(task) => write!(f, "The task {} was not found", task),
-            // DoMeError::WorkspaceNotFound(workspace) => {
-            //     write!(f, "The workspace {} was not found", workspace)
-            // }
+            DoMeError::TaskNotFound(task) => write!(f, "The task {} was not found", task),
+            DoMeError::WorkspaceNotFound(workspace) => {
+                write!(f, "The workspace {} was not found", workspace)
+            }
+            DoMeError::AmbiguousTask(task) => write!(
+                f,
+                "The task {} exists in more than one workspace, use --workspace to pick one",
+                task
+            ),
             DoMeError::TaskAlreadyExists(task) => write!(f, "The task {} already exists", task),
             DoMeError::WorkspaceAlreadyExists(workspace) => {
                 write!(f, "The workspace {} already exists", workspace)
             }
+            DoMeError::TrashItemNotFound(id) => {
+                write!(f, "The trash entry {} was not found", id)
+            }
+            DoMeError::UnsupportedDatabaseVersion(version) => write!(
+                f,
+                "The database version {} is newer than this version of do-me supports",
+                version
+            ),
+            DoMeError::UnsupportedExportVersion(version) => write!(
+                f,
+                "The export format version {} is newer than this version of do-me supports",
+                version
+            ),
         }
     }
 }
@@ -0,0 +1,607 @@
+use crate::stats::{sparkline, Stats, WorkspaceStats};
+use crate::structs::*;
+use chrono::NaiveDateTime;
+use clap::ValueEnum;
+use color_eyre::Result;
+use serde::Serialize;
+use std::io::Write;
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+/// A task as written by the listing commands, the field names are part of the output format
+/// and must not change.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct TaskRecord {
+    pub id: i32,
+    pub workspace: String,
+    pub workspace_order: usize,
+    pub order: usize,
+    pub name: String,
+    pub description: String,
+    pub priority: char,
+    pub completed: bool,
+    pub create_date: String,
+    pub due_date: Option<String>,
+    pub start_date: Option<String>,
+    /// space separated, tags never contain spaces.
+    pub tags: String,
+    pub parent_id: Option<i32>,
+    pub recurrence: Option<String>,
+    pub completed_at: Option<String>,
+    /// how deep the task is in the tree of its workspace, only used to indent the table.
+    #[serde(skip)]
+    pub depth: usize,
+}
+
+/// A workspace as written by the listing commands, the field names are part of the output
+/// format and must not change.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct WorkspaceRecord {
+    pub id: i32,
+    pub name: String,
+    pub order: usize,
+    pub tasks: usize,
+    pub completed_tasks: usize,
+    pub create_date: String,
+    pub update_date: String,
+}
+
+/// A task found by the search command, the field names are part of the output format and must
+/// not change.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct SearchRecord {
+    pub id: i32,
+    pub workspace: String,
+    pub name: String,
+    pub priority: char,
+    pub completed: bool,
+    pub snippet: String,
+}
+
+/// An entry of the trash as written by the trash command, the field names are part of the output
+/// format and must not change.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct TrashRecord {
+    pub id: i32,
+    /// `task` or `workspace`.
+    pub kind: String,
+    pub name: String,
+    /// the workspace of a task, none for a workspace.
+    pub workspace: Option<String>,
+    /// the tasks removed along with the entry.
+    pub tasks: usize,
+    pub deleted_at: String,
+}
+
+/// A change made to a task as written by the log command, the field names are part of the output
+/// format and must not change.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct EventRecord {
+    pub id: i32,
+    pub task_id: i32,
+    pub task: String,
+    /// none once the workspace is removed.
+    pub workspace: Option<String>,
+    pub kind: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub date: String,
+}
+
+/// The statistics as written by the stats command, the field names are part of the output format
+/// and must not change.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct StatsRecord {
+    pub workspaces: Vec<WorkspaceStatsRecord>,
+    /// the counts of every workspace together.
+    pub total: WorkspaceStatsRecord,
+    pub completed_per_day: Vec<DayRecord>,
+    /// the open tasks of each priority.
+    pub priorities: Vec<PriorityRecord>,
+    pub oldest_open: Vec<OpenTaskRecord>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct WorkspaceStatsRecord {
+    pub name: String,
+    pub open: usize,
+    pub done: usize,
+    /// from 0 to 1, none for a workspace without tasks.
+    pub completion_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DayRecord {
+    pub date: String,
+    pub completed: usize,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct PriorityRecord {
+    pub priority: char,
+    pub open: usize,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct OpenTaskRecord {
+    pub id: i32,
+    pub workspace: String,
+    pub name: String,
+    pub priority: char,
+    pub create_date: String,
+}
+
+fn format_datetime(date: &NaiveDateTime) -> String {
+    date.format(DATETIME_FORMAT).to_string()
+}
+
+impl TaskRecord {
+    pub fn new(workspace: &Workspace, task: &Task) -> Self {
+        Self {
+            id: task.id,
+            workspace: workspace.name.clone(),
+            workspace_order: workspace.order,
+            order: task.order,
+            name: task.name.clone(),
+            description: task.description.clone(),
+            priority: priority_letter(task.priority),
+            completed: task.completed,
+            create_date: format_datetime(&task.create_date),
+            due_date: task.due_date.as_ref().map(format_datetime),
+            start_date: task.start_date.as_ref().map(format_datetime),
+            tags: task.tags.join(" "),
+            parent_id: task.parent_id,
+            recurrence: task.recurrence.as_ref().map(Recurrence::to_string),
+            completed_at: task.completed_at.as_ref().map(format_datetime),
+            depth: 0,
+        }
+    }
+
+    pub fn with_depth(self, depth: usize) -> Self {
+        Self { depth, ..self }
+    }
+}
+
+impl SearchRecord {
+    pub fn new(result: &SearchResult) -> Self {
+        Self {
+            id: result.task.id,
+            workspace: result.workspace.clone(),
+            name: result.task.name.clone(),
+            priority: priority_letter(result.task.priority),
+            completed: result.task.completed,
+            snippet: result.snippet.clone(),
+        }
+    }
+}
+
+impl TrashRecord {
+    pub fn new(item: &TrashItem) -> Self {
+        let (kind, workspace) = match &item.content {
+            TrashContent::Task { workspace, .. } => ("task", Some(workspace.clone())),
+            TrashContent::Workspace { .. } => ("workspace", None),
+        };
+        Self {
+            id: item.id,
+            kind: kind.to_string(),
+            name: item.name().to_string(),
+            workspace,
+            tasks: item.task_count(),
+            deleted_at: format_datetime(&item.deleted_at),
+        }
+    }
+}
+
+impl EventRecord {
+    pub fn new(event: &TaskEvent) -> Self {
+        Self {
+            id: event.id,
+            task_id: event.task_id,
+            task: event.task_name.clone(),
+            workspace: event.workspace.clone(),
+            kind: event.kind.to_string(),
+            old_value: event.old_value.clone(),
+            new_value: event.new_value.clone(),
+            date: format_datetime(&event.created_at),
+        }
+    }
+}
+
+impl StatsRecord {
+    pub fn new(stats: &Stats) -> Self {
+        let workspace = |workspace: &WorkspaceStats| WorkspaceStatsRecord {
+            name: workspace.name.clone(),
+            open: workspace.open,
+            done: workspace.done,
+            completion_rate: workspace.completion_rate(),
+        };
+        Self {
+            workspaces: stats.workspaces.iter().map(workspace).collect(),
+            total: workspace(&stats.total()),
+            completed_per_day: stats
+                .completed_per_day
+                .iter()
+                .map(|(date, completed)| DayRecord {
+                    date: date.to_string(),
+                    completed: *completed,
+                })
+                .collect(),
+            priorities: (1..=4)
+                .zip(stats.priorities)
+                .map(|(priority, open)| PriorityRecord {
+                    priority: priority_letter(priority),
+                    open,
+                })
+                .collect(),
+            oldest_open: stats
+                .oldest_open
+                .iter()
+                .map(|open| OpenTaskRecord {
+                    id: open.task.id,
+                    workspace: open.workspace.clone(),
+                    name: open.task.name.clone(),
+                    priority: priority_letter(open.task.priority),
+                    create_date: format_datetime(&open.task.create_date),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl WorkspaceRecord {
+    pub fn new(workspace: &Workspace, tasks: &[Task]) -> Self {
+        Self {
+            id: workspace.id,
+            name: workspace.name.clone(),
+            order: workspace.order,
+            tasks: tasks.len(),
+            completed_tasks: tasks.iter().filter(|t| t.completed).count(),
+            create_date: format_datetime(&workspace.create_date),
+            update_date: format_datetime(&workspace.update_date),
+        }
+    }
+}
+
+pub fn write_tasks(
+    out: &mut impl Write,
+    records: &[TaskRecord],
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => write_json(out, records),
+        OutputFormat::Csv => write_csv(out, records),
+        OutputFormat::Table => {
+            let workspace_width = records.iter().map(|r| r.workspace.len()).max();
+            let name_width = records.iter().map(|r| r.depth * 2 + r.name.len()).max();
+            let id_width = records.iter().map(|r| r.id.to_string().len()).max();
+            for record in records {
+                let check = if record.completed { "x" } else { " " };
+                let due = record
+                    .due_date
+                    .as_deref()
+                    .and_then(|date| NaiveDateTime::parse_from_str(date, DATETIME_FORMAT).ok())
+                    .map(|date| format_date(&date))
+                    .unwrap_or_default();
+                // a table row holds one line, the first one stands for the description.
+                let description = match record.description.split_once('\n') {
+                    Some((first_line, _)) => format!("{first_line} …"),
+                    None => record.description.clone(),
+                };
+                let tags: Vec<String> = record
+                    .tags
+                    .split_whitespace()
+                    .map(|tag| format!("#{tag}"))
+                    .collect();
+                let line = format!(
+                    "{:>id_width$} [{}] {} {:workspace_width$} {:name_width$} {:16} {} {}",
+                    record.id,
+                    check,
+                    record.priority,
+                    record.workspace,
+                    "  ".repeat(record.depth) + &record.name,
+                    due,
+                    description,
+                    tags.join(" "),
+                    id_width = id_width.unwrap_or_default(),
+                    workspace_width = workspace_width.unwrap_or_default(),
+                    name_width = name_width.unwrap_or_default(),
+                );
+                writeln!(out, "{}", line.trim_end())?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn write_workspaces(
+    out: &mut impl Write,
+    records: &[WorkspaceRecord],
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => write_json(out, records),
+        OutputFormat::Csv => write_csv(out, records),
+        OutputFormat::Table => {
+            let name_width = records.iter().map(|r| r.name.len()).max();
+            for record in records {
+                writeln!(
+                    out,
+                    "{:name_width$} {}/{}",
+                    record.name,
+                    record.completed_tasks,
+                    record.tasks,
+                    name_width = name_width.unwrap_or_default(),
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn write_search_results(
+    out: &mut impl Write,
+    records: &[SearchRecord],
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => write_json(out, records),
+        OutputFormat::Csv => write_csv(out, records),
+        OutputFormat::Table => {
+            let workspace_width = records.iter().map(|r| r.workspace.len()).max();
+            let name_width = records.iter().map(|r| r.name.len()).max();
+            let id_width = records.iter().map(|r| r.id.to_string().len()).max();
+            for record in records {
+                let check = if record.completed { "x" } else { " " };
+                let line = format!(
+                    "{:>id_width$} [{}] {} {:workspace_width$} {:name_width$} {}",
+                    record.id,
+                    check,
+                    record.priority,
+                    record.workspace,
+                    record.name,
+                    record.snippet,
+                    id_width = id_width.unwrap_or_default(),
+                    workspace_width = workspace_width.unwrap_or_default(),
+                    name_width = name_width.unwrap_or_default(),
+                );
+                writeln!(out, "{}", line.trim_end())?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn write_trash(
+    out: &mut impl Write,
+    records: &[TrashRecord],
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => write_json(out, records),
+        OutputFormat::Csv => write_csv(out, records),
+        OutputFormat::Table => {
+            let id_width = records.iter().map(|r| r.id.to_string().len()).max();
+            let name_width = records.iter().map(|r| r.name.len()).max();
+            for record in records {
+                let line = format!(
+                    "{:>id_width$} {} {:9} {:name_width$} {}",
+                    record.id,
+                    record.deleted_at,
+                    record.kind,
+                    record.name,
+                    record.workspace.as_deref().unwrap_or_default(),
+                    id_width = id_width.unwrap_or_default(),
+                    name_width = name_width.unwrap_or_default(),
+                );
+                writeln!(out, "{}", line.trim_end())?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn write_events(
+    out: &mut impl Write,
+    records: &[EventRecord],
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => write_json(out, records),
+        OutputFormat::Csv => write_csv(out, records),
+        OutputFormat::Table => {
+            let workspace_width = records
+                .iter()
+                .map(|r| r.workspace.as_deref().unwrap_or_default().len())
+                .max();
+            let name_width = records.iter().map(|r| r.task.len()).max();
+            for record in records {
+                let change = match (&record.old_value, &record.new_value) {
+                    (None, None) => String::new(),
+                    (old, new) => format!(
+                        "{} → {}",
+                        old.as_deref().unwrap_or("-"),
+                        new.as_deref().unwrap_or("-")
+                    ),
+                };
+                let line = format!(
+                    "{} {:13} {:workspace_width$} {:name_width$} {}",
+                    record.date,
+                    record.kind,
+                    record.workspace.as_deref().unwrap_or_default(),
+                    record.task,
+                    change,
+                    workspace_width = workspace_width.unwrap_or_default(),
+                    name_width = name_width.unwrap_or_default(),
+                );
+                writeln!(out, "{}", line.trim_end())?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// writes the statistics, a CSV document only holds the workspaces.
+pub fn write_stats(out: &mut impl Write, record: &StatsRecord, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, record)?;
+            writeln!(out)?;
+            Ok(())
+        }
+        OutputFormat::Csv => write_csv(out, &record.workspaces),
+        OutputFormat::Table => {
+            let total = (record.workspaces.len() > 1).then_some(&record.total);
+            let name_width = record
+                .workspaces
+                .iter()
+                .chain(total)
+                .map(|r| r.name.len())
+                .max();
+            for workspace in record.workspaces.iter().chain(total) {
+                let rate = workspace
+                    .completion_rate
+                    .map(|rate| format!("{:.0}%", rate * 100.0))
+                    .unwrap_or("-".to_string());
+                writeln!(
+                    out,
+                    "{:name_width$} {:>4} open {:>4} done {:>4}",
+                    workspace.name,
+                    workspace.open,
+                    workspace.done,
+                    rate,
+                    name_width = name_width.unwrap_or_default(),
+                )?;
+            }
+
+            let completed: usize = record.completed_per_day.iter().map(|d| d.completed).sum();
+            writeln!(
+                out,
+                "\ncompleted in the last {} days: {completed}",
+                record.completed_per_day.len()
+            )?;
+            if let (Some(first), Some(last)) = (
+                record.completed_per_day.first(),
+                record.completed_per_day.last(),
+            ) {
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    first.date,
+                    sparkline(record.completed_per_day.iter().map(|d| d.completed)),
+                    last.date
+                )?;
+            }
+
+            let priorities: Vec<String> = record
+                .priorities
+                .iter()
+                .map(|p| format!("{} {}", p.priority, p.open))
+                .collect();
+            writeln!(out, "\nopen tasks by priority: {}", priorities.join("  "))?;
+
+            if !record.oldest_open.is_empty() {
+                writeln!(out, "\noldest open tasks:")?;
+                let workspace_width = record.oldest_open.iter().map(|r| r.workspace.len()).max();
+                for task in &record.oldest_open {
+                    writeln!(
+                        out,
+                        "{} {} {:workspace_width$} {}",
+                        &task.create_date[..10],
+                        task.priority,
+                        task.workspace,
+                        task.name,
+                        workspace_width = workspace_width.unwrap_or_default(),
+                    )?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_json<T: Serialize>(out: &mut impl Write, records: &[T]) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *out, records)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn write_csv<T: Serialize>(out: &mut impl Write, records: &[T]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn records() -> Vec<TaskRecord> {
+        let workspace = Workspace {
+            id: 1,
+            name: "work".into(),
+            ..Default::default()
+        };
+        let task = Task {
+            id: 7,
+            name: "report, final".into(),
+            priority: 1,
+            completed: true,
+            due_date: parse_date_input("2024-05-01"),
+            workspace_id: 1,
+            tags: vec!["bug".into(), "urgent".into()],
+            parent_id: Some(3),
+            recurrence: "weekly fri,mon".parse().ok(),
+            completed_at: parse_date_input("2024-04-30 18:30"),
+            ..Default::default()
+        };
+        vec![TaskRecord::new(&workspace, &task)]
+    }
+
+    #[test]
+    fn test_tasks_json() {
+        let mut out = Vec::new();
+        write_tasks(&mut out, &records(), OutputFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([{
+                "id": 7,
+                "workspace": "work",
+                "workspace_order": 0,
+                "order": 0,
+                "name": "report, final",
+                "description": "",
+                "priority": "A",
+                "completed": true,
+                "create_date": "1970-01-01 00:00:00",
+                "due_date": "2024-05-01 00:00:00",
+                "start_date": null,
+                "tags": "bug urgent",
+                "parent_id": 3,
+                "recurrence": "weekly mon,fri",
+                "completed_at": "2024-04-30 18:30:00",
+            }])
+        );
+    }
+
+    #[test]
+    fn test_tasks_csv() {
+        let mut out = Vec::new();
+        write_tasks(&mut out, &records(), OutputFormat::Csv).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "id,workspace,workspace_order,order,name,description,priority,completed,create_date,due_date,start_date,tags,parent_id,recurrence,completed_at\n\
+             7,work,0,0,\"report, final\",,A,true,1970-01-01 00:00:00,2024-05-01 00:00:00,,bug urgent,3,\"weekly mon,fri\",2024-04-30 18:30:00\n"
+        );
+    }
+}
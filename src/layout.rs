@@ -0,0 +1,229 @@
+use color_eyre::Result;
+use ratatui::layout::{Constraint, Layout, Rect};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, fs, path::Path, str::FromStr};
+use strum::Display;
+
+/// where the layout changed at runtime is kept, in the data directory.
+pub const LAYOUT_FILE: &str = "layout.json";
+
+/// how the workspaces pane sits next to the tasks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Orientation {
+    /// beside the tasks, above them when the terminal is narrower than `stack_below`.
+    #[default]
+    Auto,
+    Horizontal,
+    Vertical,
+}
+
+impl Orientation {
+    pub fn next(self) -> Self {
+        match self {
+            Orientation::Auto => Orientation::Horizontal,
+            Orientation::Horizontal => Orientation::Vertical,
+            Orientation::Vertical => Orientation::Auto,
+        }
+    }
+}
+
+/// the size of the workspaces pane, in cells (`20`) or in percent of the screen (`25%`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneSize {
+    Fixed(u16),
+    Percent(u16),
+}
+
+impl PaneSize {
+    fn constraint(self) -> Constraint {
+        match self {
+            PaneSize::Fixed(size) => Constraint::Length(size),
+            PaneSize::Percent(percent) => Constraint::Percentage(percent),
+        }
+    }
+
+    /// grows by `steps` of `cells` or of 5%, or shrinks when negative, keeping `min` cells and
+    /// a third of `total` for the tasks.
+    fn resize(self, steps: i16, cells: i16, min: u16, total: u16) -> Self {
+        match self {
+            PaneSize::Fixed(size) => {
+                let max = (total / 3 * 2).max(min);
+                PaneSize::Fixed(size.saturating_add_signed(steps * cells).clamp(min, max))
+            }
+            PaneSize::Percent(percent) => {
+                PaneSize::Percent(percent.saturating_add_signed(steps * 5).clamp(5, 70))
+            }
+        }
+    }
+}
+
+impl FromStr for PaneSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (number, percent) = match s.strip_suffix('%') {
+            Some(number) => (number.trim_end(), true),
+            None => (s, false),
+        };
+        let size: u16 = number
+            .parse()
+            .map_err(|_| format!("`{s}` is not a size like `20` or `25%`"))?;
+        match (percent, size) {
+            (true, 0..=100) => Ok(PaneSize::Percent(size)),
+            (true, _) => Err(format!("`{s}` is more than 100%")),
+            (false, _) => Ok(PaneSize::Fixed(size)),
+        }
+    }
+}
+
+impl fmt::Display for PaneSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PaneSize::Fixed(size) => write!(f, "{size}"),
+            PaneSize::Percent(percent) => write!(f, "{percent}%"),
+        }
+    }
+}
+
+impl Serialize for PaneSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PaneSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // a bare number is a size in cells.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u16),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(size) => Ok(PaneSize::Fixed(size)),
+            Raw::Text(text) => text.parse().map_err(de::Error::custom),
+        }
+    }
+}
+
+/// how the screen is shared between the workspaces and the tasks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaneLayout {
+    #[serde(rename = "layout")]
+    pub orientation: Orientation,
+    /// the width of the workspaces pane beside the tasks.
+    pub workspace_width: PaneSize,
+    /// the height of the workspaces pane above the tasks.
+    pub workspace_height: PaneSize,
+    /// the width under which the automatic layout stacks the panes.
+    pub stack_below: u16,
+    pub hide_workspaces: bool,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            orientation: Orientation::Auto,
+            workspace_width: PaneSize::Fixed(20),
+            workspace_height: PaneSize::Percent(30),
+            stack_below: 80,
+            hide_workspaces: false,
+        }
+    }
+}
+
+impl PaneLayout {
+    /// the layout saved by `save`, if any.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// whether the workspaces go above the tasks on a screen `width` wide.
+    pub fn is_stacked(&self, width: u16) -> bool {
+        match self.orientation {
+            Orientation::Auto => width < self.stack_below,
+            Orientation::Horizontal => false,
+            Orientation::Vertical => true,
+        }
+    }
+
+    /// the areas of the workspaces and the tasks, the workspaces get an empty one when hidden.
+    pub fn areas(&self, area: Rect) -> [Rect; 2] {
+        if self.hide_workspaces {
+            return [Rect::new(area.x, area.y, 0, 0), area];
+        }
+        if self.is_stacked(area.width) {
+            Layout::vertical([self.workspace_height.constraint(), Constraint::Fill(1)]).areas(area)
+        } else {
+            Layout::horizontal([self.workspace_width.constraint(), Constraint::Fill(1)]).areas(area)
+        }
+    }
+
+    /// grows the workspaces pane by `steps` along the way it is split from the tasks, or shrinks
+    /// it when negative. a hidden pane is shown again.
+    pub fn resize(&mut self, steps: i16, area: Rect) {
+        self.hide_workspaces = false;
+        if self.is_stacked(area.width) {
+            self.workspace_height = self.workspace_height.resize(steps, 1, 3, area.height);
+        } else {
+            self.workspace_width = self.workspace_width.resize(steps, 2, 8, area.width);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pane_size() {
+        assert_eq!("20".parse(), Ok(PaneSize::Fixed(20)));
+        assert_eq!(" 25 %".parse(), Ok(PaneSize::Percent(25)));
+        assert!("120%".parse::<PaneSize>().is_err());
+        assert!("wide".parse::<PaneSize>().is_err());
+        let layout: PaneLayout =
+            serde_json::from_str(r#"{"workspace_width": 30, "workspace_height": "40%"}"#).unwrap();
+        assert_eq!(layout.workspace_width, PaneSize::Fixed(30));
+        assert_eq!(layout.workspace_height, PaneSize::Percent(40));
+        assert_eq!(
+            serde_json::from_str::<PaneLayout>(&serde_json::to_string(&layout).unwrap()).unwrap(),
+            layout
+        );
+    }
+
+    #[test]
+    fn test_areas() {
+        let mut layout = PaneLayout::default();
+        let [workspaces, tasks] = layout.areas(Rect::new(0, 0, 100, 30));
+        assert_eq!(workspaces, Rect::new(0, 0, 20, 30));
+        assert_eq!(tasks, Rect::new(20, 0, 80, 30));
+
+        // narrow screens stack the panes.
+        let [workspaces, tasks] = layout.areas(Rect::new(0, 0, 60, 30));
+        assert_eq!(workspaces, Rect::new(0, 0, 60, 9));
+        assert_eq!(tasks, Rect::new(0, 9, 60, 21));
+
+        layout.resize(-1, Rect::new(0, 0, 100, 30));
+        assert_eq!(layout.workspace_width, PaneSize::Fixed(18));
+        layout.hide_workspaces = true;
+        let [workspaces, tasks] = layout.areas(Rect::new(0, 0, 100, 30));
+        assert!(workspaces.is_empty());
+        assert_eq!(tasks, Rect::new(0, 0, 100, 30));
+    }
+}
@@ -3,15 +3,21 @@ use cli::Cli;
 use color_eyre::Result;
 
 use crate::app::App;
+use crate::config::Config;
+use crate::database_ops::{DatabaseOperations, DATABASE_FILE};
 
 mod action;
 mod app;
 mod cli;
+mod commands;
 mod components;
 mod config;
 mod database_ops;
 mod errors;
+mod formats;
+mod layout;
 mod logging;
+mod stats;
 mod structs;
 mod tui;
 
@@ -21,6 +27,16 @@ async fn main() -> Result<()> {
     crate::logging::init()?;
 
     let args = Cli::parse();
+    if let Some(command) = args.command {
+        let config = Config::new()?;
+        let db = DatabaseOperations::new(config.config.data_dir.join(DATABASE_FILE));
+        // scripts only need the message, not the report meant for bug hunting.
+        if let Err(e) = commands::run(command, &db) {
+            eprintln!("error: {e}");
+            std::process::exit(libc::EXIT_FAILURE);
+        }
+        return Ok(());
+    }
     let mut app = App::new(args.tick_rate, args.frame_rate)?;
     app.run().await?;
     Ok(())
@@ -0,0 +1,295 @@
+use crate::database_ops::DatabaseOperations;
+use crate::structs::*;
+use chrono::{Days, NaiveDate, NaiveDateTime};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// number of days the completions are counted over by default.
+pub const STATS_DAYS: u16 = 28;
+
+/// number of open tasks listed as the oldest ones.
+const OLDEST_OPEN_TASKS: usize = 5;
+
+/// the tasks of a workspace by completion.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceStats {
+    pub name: String,
+    pub open: usize,
+    pub done: usize,
+}
+
+impl WorkspaceStats {
+    /// the share of the tasks that are done, none without tasks.
+    pub fn completion_rate(&self) -> Option<f64> {
+        let total = self.open + self.done;
+        (total > 0).then(|| self.done as f64 / total as f64)
+    }
+}
+
+/// An open task and the workspace it is in.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenTask {
+    pub task: Task,
+    pub workspace: String,
+}
+
+/// What the tasks of every workspace look like and how many got done lately.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    /// the workspaces in their order.
+    pub workspaces: Vec<WorkspaceStats>,
+    /// the tasks completed each day, the last day is today.
+    pub completed_per_day: Vec<(NaiveDate, usize)>,
+    /// the open tasks of each priority, from A to D.
+    pub priorities: [usize; 4],
+    /// the tasks open for the longest time, the oldest first.
+    pub oldest_open: Vec<OpenTask>,
+}
+
+impl Stats {
+    /// the statistics of `workspaces` with their tasks, the completions are counted from the
+    /// `events` of the last `days` up to `now` and from the completion date the tasks keep.
+    pub fn new(
+        workspaces: &[(Workspace, Vec<Task>)],
+        events: &[TaskEvent],
+        now: NaiveDateTime,
+        days: usize,
+    ) -> Self {
+        let today = now.date();
+        let first_day = today - Days::new(days.saturating_sub(1) as u64);
+        let mut completed_per_day: Vec<(NaiveDate, usize)> = first_day
+            .iter_days()
+            .take(days)
+            .map(|day| (day, 0))
+            .collect();
+        // a task reopened takes back its last completion, like one checked by mistake.
+        let mut completions: HashMap<i32, Vec<NaiveDate>> = HashMap::new();
+        let mut events: Vec<&TaskEvent> = events.iter().collect();
+        events.sort_by_key(|event| (event.created_at, event.id));
+        for event in events {
+            match event.kind {
+                TaskEventKind::Completed => completions
+                    .entry(event.task_id)
+                    .or_default()
+                    .push(event.created_at.date()),
+                TaskEventKind::Reopened => {
+                    completions.entry(event.task_id).or_default().pop();
+                }
+                _ => {}
+            }
+        }
+        // a task without completions in the events still counts on the date it was completed,
+        // the tasks completed before they kept one only count as done.
+        for task in workspaces.iter().flat_map(|(_, tasks)| tasks) {
+            if let Some(completed_at) = task.completed_at {
+                completions
+                    .entry(task.id)
+                    .or_insert_with(|| vec![completed_at.date()]);
+            }
+        }
+        for day in completions.into_values().flatten() {
+            if let Some((_, count)) = completed_per_day.iter_mut().find(|(d, _)| *d == day) {
+                *count += 1;
+            }
+        }
+
+        let mut priorities = [0; 4];
+        let mut oldest_open = Vec::new();
+        let mut workspace_stats = Vec::new();
+        for (workspace, tasks) in workspaces {
+            let done = tasks.iter().filter(|task| task.completed).count();
+            workspace_stats.push(WorkspaceStats {
+                name: workspace.name.clone(),
+                open: tasks.len() - done,
+                done,
+            });
+            for task in tasks.iter().filter(|task| !task.completed) {
+                priorities[task.priority.clamp(1, 4) as usize - 1] += 1;
+                oldest_open.push(OpenTask {
+                    task: task.clone(),
+                    workspace: workspace.name.clone(),
+                });
+            }
+        }
+        oldest_open.sort_by_key(|open| (open.task.create_date, open.task.id));
+        oldest_open.truncate(OLDEST_OPEN_TASKS);
+
+        Self {
+            workspaces: workspace_stats,
+            completed_per_day,
+            priorities,
+            oldest_open,
+        }
+    }
+
+    /// the statistics of the whole database.
+    pub fn collect(db: &DatabaseOperations, now: NaiveDateTime, days: usize) -> Result<Self> {
+        let mut workspaces = db.get_workspaces()?;
+        WorkspaceSorter::default().sort(&mut workspaces);
+        let mut workspace_tasks = Vec::with_capacity(workspaces.len());
+        for workspace in workspaces {
+            let tasks = db.get_tasks(workspace.id)?;
+            workspace_tasks.push((workspace, tasks));
+        }
+        let first_day = now.date() - Days::new(days.saturating_sub(1) as u64);
+        let events = db.get_events_since(Some(first_day.and_time(Default::default())))?;
+        Ok(Self::new(&workspace_tasks, &events, now, days))
+    }
+
+    /// the counts of every workspace together.
+    pub fn total(&self) -> WorkspaceStats {
+        WorkspaceStats {
+            name: "total".to_string(),
+            open: self.workspaces.iter().map(|w| w.open).sum(),
+            done: self.workspaces.iter().map(|w| w.done).sum(),
+        }
+    }
+
+    pub fn completed(&self) -> usize {
+        self.completed_per_day.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// the counts drawn with one block character each, the highest count gets a full block.
+pub fn sparkline(counts: impl IntoIterator<Item = usize>) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let counts: Vec<usize> = counts.into_iter().collect();
+    let max = counts.iter().copied().max().unwrap_or_default().max(1);
+    counts
+        .iter()
+        .map(|&count| match count {
+            0 => ' ',
+            count => BARS[(count * BARS.len()).div_ceil(max) - 1],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database_ops::transfer::{ConflictPolicy, ImportMode};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_stats() {
+        let date = |input| parse_date_input(input).unwrap();
+        let task = |id, priority, completed, create_date| Task {
+            id,
+            name: format!("task {id}"),
+            priority,
+            completed,
+            create_date: date(create_date),
+            ..Default::default()
+        };
+        let workspace = |name: &str| Workspace {
+            name: name.into(),
+            ..Default::default()
+        };
+        let workspaces = [
+            (
+                workspace("work"),
+                vec![
+                    task(1, 1, false, "2024-05-03"),
+                    task(2, 3, true, "2024-05-01"),
+                    task(3, 3, false, "2024-05-02"),
+                ],
+            ),
+            (workspace("home"), vec![task(4, 4, false, "2024-04-01")]),
+            (workspace("empty"), Vec::new()),
+        ];
+        let event = |task_id, kind, created_at| TaskEvent {
+            id: 0,
+            task_id,
+            task_name: format!("task {task_id}"),
+            workspace: None,
+            kind,
+            old_value: None,
+            new_value: None,
+            created_at: date(created_at),
+        };
+        let events = [
+            event(2, TaskEventKind::Completed, "2024-05-05 10:00"),
+            event(2, TaskEventKind::Completed, "2024-05-07 09:00"),
+            event(1, TaskEventKind::Completed, "2024-05-07 18:00"),
+            // the last completion of task 1 was a mistake.
+            event(1, TaskEventKind::Completed, "2024-05-07 18:05"),
+            event(1, TaskEventKind::Reopened, "2024-05-07 18:06"),
+        ];
+        let stats = Stats::new(&workspaces, &events, date("2024-05-07 20:00"), 3);
+
+        assert_eq!(
+            stats.completed_per_day,
+            vec![
+                (date("2024-05-05").date(), 1),
+                (date("2024-05-06").date(), 0),
+                (date("2024-05-07").date(), 2),
+            ]
+        );
+        assert_eq!(stats.completed(), 3);
+        assert_eq!(stats.priorities, [1, 0, 1, 1]);
+        let oldest: Vec<i32> = stats.oldest_open.iter().map(|open| open.task.id).collect();
+        assert_eq!(oldest, vec![4, 3, 1]);
+        assert_eq!(stats.workspaces[0].completion_rate(), Some(1.0 / 3.0));
+        assert_eq!(stats.workspaces[2].completion_rate(), None);
+        assert_eq!((stats.total().open, stats.total().done), (3, 1));
+        assert_eq!(sparkline([0, 1, 4, 2]), " ▂█▄");
+    }
+
+    #[test]
+    fn test_collect() {
+        let date = |input| parse_date_input(input).unwrap();
+        let db = DatabaseOperations::new(":memory:".into());
+        let exported = |name: &str, completed_at: Option<&str>| ExportTask {
+            name: name.into(),
+            priority: 2,
+            completed: completed_at.is_some(),
+            create_date: parse_date_input("2024-04-01"),
+            completed_at: completed_at.and_then(parse_date_input),
+            ..Default::default()
+        };
+        let document = ExportDocument {
+            version: 1,
+            workspaces: vec![ExportWorkspace {
+                name: "work".into(),
+                tasks: vec![
+                    exported("report", Some("2024-05-05 10:00")),
+                    exported("slides", Some("2024-03-01 10:00")),
+                    exported("budget", None),
+                ],
+            }],
+            ..Default::default()
+        };
+        db.import(document, ImportMode::Merge, ConflictPolicy::Fail)
+            .unwrap();
+        let work = db.search_workspace_name("work").unwrap().unwrap();
+        let budget = db.search_task_name("budget", work).unwrap().unwrap();
+        let task = db.get_task(budget).unwrap().unwrap();
+        db.handle_update_task(task.toggle_completion(date("2024-05-07 09:00")))
+            .unwrap();
+        // a task brought back with its completion date but without the event of it.
+        let mut restored = db.get_task(budget).unwrap().unwrap();
+        restored.id = 100;
+        restored.name = "restored".into();
+        restored.completed_at = Some(date("2024-05-06 12:00"));
+        db.restore_task(&restored).unwrap();
+
+        let stats = Stats::collect(&db, date("2024-05-07 20:00"), 3).unwrap();
+        assert_eq!(
+            stats.completed_per_day,
+            vec![
+                (date("2024-05-05").date(), 1),
+                (date("2024-05-06").date(), 1),
+                (date("2024-05-07").date(), 1),
+            ]
+        );
+        assert_eq!(
+            stats.workspaces,
+            vec![WorkspaceStats {
+                name: "work".into(),
+                open: 0,
+                done: 4,
+            }]
+        );
+    }
+}
@@ -1,5 +1,16 @@
-use chrono::NaiveDateTime;
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+use strum::{Display, EnumString};
+
+/// the format used to store dates in the database.
+pub const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct Task {
@@ -9,8 +20,292 @@ pub struct Task {
     pub priority: i32,
     pub completed: bool,
     pub create_date: NaiveDateTime,
+    pub due_date: Option<NaiveDateTime>,
+    pub start_date: Option<NaiveDateTime>,
+    /// the order among the tasks sharing the parent.
     pub order: usize,
     pub workspace_id: i32,
+    /// sorted names of the tags of the task.
+    pub tags: Vec<String>,
+    pub parent_id: Option<i32>,
+    pub recurrence: Option<Recurrence>,
+    /// the name of the status, `None` for the first status of its completion.
+    pub status: Option<String>,
+    /// when the task was last completed, a recurring task keeps the date of its last occurrence.
+    #[serde(default)]
+    pub completed_at: Option<NaiveDateTime>,
+}
+
+impl Task {
+    /// a task is overdue when it is not completed and its due date has passed.
+    /// a due date without a time (midnight) covers the whole day.
+    pub fn is_overdue(&self, now: NaiveDateTime) -> bool {
+        match self.due_date {
+            Some(_) if self.completed => false,
+            Some(due) if due.time() == NaiveTime::MIN => due.date() < now.date(),
+            Some(due) => due < now,
+            None => false,
+        }
+    }
+
+    /// the update toggling the completion of the task, completing a recurring task reschedules it
+    /// to its next occurrence instead.
+    pub fn toggle_completion(&self, now: NaiveDateTime) -> UpdateTask {
+        match &self.recurrence {
+            Some(recurrence) if !self.completed => {
+                let due_date = recurrence.next(self.due_date, now);
+                // the start date keeps its distance to the due date.
+                let start_date = self
+                    .start_date
+                    .map(|start| start + (due_date - self.due_date.unwrap_or(due_date)));
+                UpdateTask {
+                    id: self.id,
+                    due_date: Some(Some(due_date)),
+                    start_date: Some(start_date),
+                    status: Some(None),
+                    completed_at: Some(Some(now)),
+                    ..Default::default()
+                }
+            }
+            _ => UpdateTask {
+                id: self.id,
+                completed: Some(!self.completed),
+                completed_at: (!self.completed).then_some(Some(now)),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// the changes giving the status `name` to the task, completed when the status is `done`. a
+    /// recurring task reaching a done status moves on to its next occurrence instead, like when
+    /// it is checked.
+    pub fn set_status(&self, name: &str, done: bool, now: NaiveDateTime) -> UpdateTask {
+        if done && !self.completed && self.recurrence.is_some() {
+            return self.toggle_completion(now);
+        }
+        UpdateTask {
+            id: self.id,
+            completed: Some(done),
+            status: Some(Some(name.to_string())),
+            ..Default::default()
+        }
+    }
+}
+
+/// the letter shown for a priority, 1 is `A` and 4 is `D`.
+pub fn priority_letter(priority: i32) -> char {
+    (b'A' + (priority.clamp(1, 4) - 1) as u8) as char
+}
+
+/// parses a priority written as a letter from `A` to `D` or a number from 1 to 4.
+pub fn parse_priority(input: &str) -> Option<i32> {
+    match input.trim().to_ascii_uppercase().as_str() {
+        "A" | "1" => Some(1),
+        "B" | "2" => Some(2),
+        "C" | "3" => Some(3),
+        "D" | "4" => Some(4),
+        _ => None,
+    }
+}
+
+/// formats a due or start date, the time is omitted when it is midnight.
+pub fn format_date(date: &NaiveDateTime) -> String {
+    if date.time() == NaiveTime::MIN {
+        date.format("%Y-%m-%d").to_string()
+    } else {
+        date.format("%Y-%m-%d %H:%M").to_string()
+    }
+}
+
+/// parses a date typed by the user, accepts `YYYY-MM-DD` and `YYYY-MM-DD HH:MM`.
+pub fn parse_date_input(input: &str) -> Option<NaiveDateTime> {
+    let input = input.trim();
+    if let Ok(date) = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") {
+        return Some(date);
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .ok()
+        .map(|date| date.and_time(NaiveTime::MIN))
+}
+
+/// the most days `every N days` can wait, about a hundred years.
+const MAX_RECURRENCE_DAYS: u32 = 36500;
+
+/// How a recurring task comes back once completed, written the way it is typed:
+/// `daily`, `weekly mon,fri`, `monthly` or `every 3 days`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Recurrence {
+    Daily,
+    /// on the given weekdays, on the weekday of the due date when there are none.
+    Weekly(Vec<Weekday>),
+    /// on the day of the month of the due date.
+    Monthly,
+    /// a number of days after the task is completed.
+    AfterCompletion(u32),
+}
+
+impl Recurrence {
+    /// the due date of the next occurrence of a task completed at `now`. the occurrences that were
+    /// missed are skipped, a task without a due date recurs from today.
+    pub fn next(&self, due: Option<NaiveDateTime>, now: NaiveDateTime) -> NaiveDateTime {
+        let due = due.unwrap_or_else(|| now.date().and_time(NaiveTime::MIN));
+        if let Recurrence::AfterCompletion(days) = self {
+            // the last date there is, rather than a crash, for a date too far away.
+            return now
+                .date()
+                .checked_add_days(Days::new(*days as u64))
+                .unwrap_or(NaiveDate::MAX)
+                .and_time(due.time());
+        }
+        let mut next = due;
+        let mut months = 0;
+        while next <= due || next.date() <= now.date() {
+            next = match self {
+                Recurrence::Daily => next + Days::new(1),
+                Recurrence::Weekly(weekdays) if weekdays.is_empty() => next + Days::new(7),
+                Recurrence::Weekly(weekdays) => {
+                    let mut day = next + Days::new(1);
+                    while !weekdays.contains(&day.weekday()) {
+                        day = day + Days::new(1);
+                    }
+                    day
+                }
+                // counting from the due date keeps the day of the month after a shorter month.
+                Recurrence::Monthly => {
+                    months += 1;
+                    due + Months::new(months)
+                }
+                Recurrence::AfterCompletion(_) => unreachable!(),
+            };
+        }
+        next
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recurrence::Daily => write!(f, "daily"),
+            Recurrence::Weekly(weekdays) if weekdays.is_empty() => write!(f, "weekly"),
+            Recurrence::Weekly(weekdays) => {
+                let weekdays: Vec<String> = weekdays
+                    .iter()
+                    .map(|day| day.to_string().to_lowercase())
+                    .collect();
+                write!(f, "weekly {}", weekdays.join(","))
+            }
+            Recurrence::Monthly => write!(f, "monthly"),
+            Recurrence::AfterCompletion(1) => write!(f, "every 1 day"),
+            Recurrence::AfterCompletion(days) => write!(f, "every {days} days"),
+        }
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim().to_lowercase();
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let recurrence = match words[..] {
+            ["daily"] => Recurrence::Daily,
+            ["monthly"] => Recurrence::Monthly,
+            ["weekly"] => Recurrence::Weekly(Vec::new()),
+            ["weekly", ref weekdays @ ..] => {
+                let mut weekdays = weekdays
+                    .iter()
+                    .flat_map(|days| days.split(','))
+                    .filter(|day| !day.is_empty())
+                    .map(Weekday::from_str)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| format!("invalid weekday in `{input}`"))?;
+                weekdays.sort_by_key(Weekday::num_days_from_monday);
+                weekdays.dedup();
+                Recurrence::Weekly(weekdays)
+            }
+            ["every", days, "day" | "days"] => match days.parse() {
+                Ok(days) if (1..=MAX_RECURRENCE_DAYS).contains(&days) => {
+                    Recurrence::AfterCompletion(days)
+                }
+                _ => return Err(format!("invalid number of days in `{input}`")),
+            },
+            _ => return Err(format!("invalid recurrence `{input}`")),
+        };
+        Ok(recurrence)
+    }
+}
+
+impl TryFrom<String> for Recurrence {
+    type Error = String;
+
+    fn try_from(input: String) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+}
+
+impl From<Recurrence> for String {
+    fn from(recurrence: Recurrence) -> Self {
+        recurrence.to_string()
+    }
+}
+
+/// the tasks in the order of the tree, every task is followed by its subtasks. each task comes
+/// with its depth, the tasks whose parent is not in `tasks` are at the top level.
+pub fn task_tree(tasks: &[Task]) -> Vec<(usize, &Task)> {
+    let ids: HashSet<i32> = tasks.iter().map(|task| task.id).collect();
+    let mut children: HashMap<Option<i32>, Vec<&Task>> = HashMap::new();
+    for task in tasks {
+        let parent = task.parent_id.filter(|id| ids.contains(id));
+        children.entry(parent).or_default().push(task);
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|task| task.order);
+    }
+
+    let mut tree = Vec::with_capacity(tasks.len());
+    let mut stack: Vec<(usize, &Task)> = children
+        .get(&None)
+        .map(|roots| roots.iter().rev().map(|task| (0, *task)).collect())
+        .unwrap_or_default();
+    while let Some((depth, task)) = stack.pop() {
+        tree.push((depth, task));
+        if let Some(subtasks) = children.get(&Some(task.id)) {
+            stack.extend(subtasks.iter().rev().map(|subtask| (depth + 1, *subtask)));
+        }
+    }
+    tree
+}
+
+/// the tag of a `#tag` word, tags are lowercase so `#Bug` and `#bug` are the same tag.
+pub fn parse_tag(word: &str) -> Option<String> {
+    let tag = word.strip_prefix('#')?;
+    (!tag.is_empty() && !tag.contains('#')).then(|| tag.to_lowercase())
+}
+
+/// sorts the tags and removes the duplicates.
+pub fn normalize_tags(tags: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut tags: Vec<String> = tags.into_iter().map(|tag| tag.to_lowercase()).collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// splits the `#tag` words out of a typed task name, returns the name and its tags.
+pub fn split_tags(input: &str) -> (String, Vec<String>) {
+    let (tags, words): (Vec<&str>, Vec<&str>) = input
+        .split_whitespace()
+        .partition(|word| parse_tag(word).is_some());
+    let tags = normalize_tags(tags.into_iter().filter_map(parse_tag));
+    (words.join(" "), tags)
+}
+
+/// writes the tags the way they are typed, `#bug #review`.
+pub fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| format!("#{tag}"))
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -34,7 +329,10 @@ pub enum TaskSortType {
     Order,
     Priority,
     Completion,
+    /// in the order of the statuses in the configuration.
+    Status,
     CreateDate,
+    DueDate,
     Name,
     Description,
 }
@@ -75,12 +373,34 @@ impl WorkspaceSorter {
     }
 }
 
+impl fmt::Display for TaskSorter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sort_type = match self.sort_type {
+            TaskSortType::Order => "order",
+            TaskSortType::Priority => "priority",
+            TaskSortType::Completion => "completion",
+            TaskSortType::Status => "status",
+            TaskSortType::CreateDate => "date created",
+            TaskSortType::DueDate => "due date",
+            TaskSortType::Name => "name",
+            TaskSortType::Description => "description",
+        };
+        write!(f, "{sort_type} {}", if self.desc { "↓" } else { "↑" })
+    }
+}
+
 impl TaskSorter {
     pub fn new(sort_type: TaskSortType, desc: bool) -> Self {
         Self { sort_type, desc }
     }
 
+    /// sorts the tasks, sorting by status puts the open tasks before the completed ones.
     pub fn sort(&self, tasks: &mut [Task]) {
+        self.sort_with_status_rank(tasks, |task| usize::from(task.completed));
+    }
+
+    /// sorts the tasks, `status_rank` gives the place of the status of a task.
+    pub fn sort_with_status_rank(&self, tasks: &mut [Task], status_rank: impl Fn(&Task) -> usize) {
         let cmp_func = |a: &Task, b: &Task| {
             let mut order = match self.sort_type {
                 TaskSortType::Priority => a.priority.cmp(&b.priority),
@@ -89,6 +409,14 @@ impl TaskSorter {
                 TaskSortType::Name => a.name.cmp(&b.name),
                 TaskSortType::Description => a.description.cmp(&b.description),
                 TaskSortType::Completion => a.completed.cmp(&b.completed),
+                TaskSortType::Status => status_rank(a).cmp(&status_rank(b)),
+                // tasks without a due date are kept after the ones that have one.
+                TaskSortType::DueDate => match (a.due_date, b.due_date) {
+                    (Some(a), Some(b)) => a.cmp(&b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                },
             };
             if self.desc {
                 order = order.reverse();
@@ -104,8 +432,15 @@ pub struct AddTask {
     pub name: String,
     pub description: Option<String>,
     pub priority: Option<i32>,
+    pub due_date: Option<NaiveDateTime>,
+    pub start_date: Option<NaiveDateTime>,
+    /// defaults to the current time.
+    pub create_date: Option<NaiveDateTime>,
     pub order: Option<usize>,
     pub workspace_id: i32,
+    pub tags: Vec<String>,
+    pub parent_id: Option<i32>,
+    pub recurrence: Option<Recurrence>,
 }
 
 #[derive(Default, Debug, Serialize, Clone, PartialEq, Eq, Deserialize)]
@@ -121,7 +456,24 @@ pub struct UpdateTask {
     pub description: Option<String>,
     pub priority: Option<i32>,
     pub completed: Option<bool>,
+    /// `Some(None)` clears the due date.
+    pub due_date: Option<Option<NaiveDateTime>>,
+    /// `Some(None)` clears the start date.
+    pub start_date: Option<Option<NaiveDateTime>>,
     pub order: Option<usize>,
+    /// replaces all the tags of the task.
+    pub tags: Option<Vec<String>>,
+    /// `Some(None)` makes the task a top level task, the task goes last among its new siblings
+    /// unless `order` is given.
+    pub parent_id: Option<Option<i32>>,
+    /// `Some(None)` makes the task not recurring.
+    pub recurrence: Option<Option<Recurrence>>,
+    /// `Some(None)` gives the task the first status of its completion, which it also gets when
+    /// `completed` changes without a status.
+    pub status: Option<Option<String>>,
+    /// `Some(None)` clears the completion date. a task that gets completed without it is
+    /// completed now, and a reopened one loses it.
+    pub completed_at: Option<Option<NaiveDateTime>>,
 }
 
 #[derive(Default, Debug, Serialize, Clone, PartialEq, Eq, Deserialize)]
@@ -130,3 +482,128 @@ pub struct UpdateWorkspace {
     pub name: Option<String>,
     pub order: Option<usize>,
 }
+
+/// A portable copy of the whole database, workspaces and tasks are stored in their order.
+#[derive(Default, Debug, Serialize, Clone, PartialEq, Eq, Deserialize)]
+pub struct ExportDocument {
+    pub version: u32,
+    pub exported_at: NaiveDateTime,
+    pub workspaces: Vec<ExportWorkspace>,
+}
+
+#[derive(Default, Debug, Serialize, Clone, PartialEq, Eq, Deserialize)]
+pub struct ExportWorkspace {
+    pub name: String,
+    pub tasks: Vec<ExportTask>,
+}
+
+#[derive(Default, Debug, Serialize, Clone, PartialEq, Eq, Deserialize)]
+pub struct ExportTask {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_priority")]
+    pub priority: i32,
+    #[serde(default)]
+    pub completed: bool,
+    pub create_date: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub due_date: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub start_date: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub completed_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub subtasks: Vec<ExportTask>,
+}
+
+fn default_priority() -> i32 {
+    3
+}
+
+/// What was removed and went to the trash, with the tasks removed along with it.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Deserialize)]
+pub enum TrashContent {
+    Task {
+        task: Task,
+        subtasks: Vec<Task>,
+        /// the name of the workspace when the task was removed.
+        workspace: String,
+    },
+    Workspace {
+        workspace: Workspace,
+        tasks: Vec<Task>,
+    },
+}
+
+/// An entry of the trash, it can be restored or purged for good.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Deserialize)]
+pub struct TrashItem {
+    pub id: i32,
+    pub deleted_at: NaiveDateTime,
+    pub content: TrashContent,
+}
+
+impl TrashItem {
+    pub fn name(&self) -> &str {
+        match &self.content {
+            TrashContent::Task { task, .. } => &task.name,
+            TrashContent::Workspace { workspace, .. } => &workspace.name,
+        }
+    }
+
+    /// the tasks that come back with the item when it is restored, the item itself excluded.
+    pub fn task_count(&self) -> usize {
+        match &self.content {
+            TrashContent::Task { subtasks, .. } => subtasks.len(),
+            TrashContent::Workspace { tasks, .. } => tasks.len(),
+        }
+    }
+}
+
+/// A task found by the full-text search, with the name of its workspace and an excerpt of the
+/// matching text.
+#[derive(Default, Debug, Serialize, Clone, PartialEq, Eq, Deserialize)]
+pub struct SearchResult {
+    pub task: Task,
+    pub workspace: String,
+    pub snippet: String,
+}
+
+/// What happened to a task, the kinds are stored by name in the history.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, Serialize, Deserialize, ValueEnum,
+)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TaskEventKind {
+    Created,
+    Renamed,
+    /// the priority changed, the values are the letters of the priorities.
+    Reprioritized,
+    Completed,
+    Reopened,
+    /// the task went under another parent, the values are the names of the parents.
+    Moved,
+}
+
+/// An entry of the history of a task, the history is only ever appended to.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Deserialize)]
+pub struct TaskEvent {
+    pub id: i32,
+    pub task_id: i32,
+    /// the name of the task when the event happened.
+    pub task_name: String,
+    /// the name of the workspace of the task, none once the workspace is removed.
+    pub workspace: Option<String>,
+    pub kind: TaskEventKind,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: NaiveDateTime,
+}